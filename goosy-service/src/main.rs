@@ -1,23 +1,33 @@
-use anchor_client::anchor_lang::AccountDeserialize;
 use anchor_client::anchor_lang::__private::base64::decode;
+use anchor_client::anchor_lang::AccountDeserialize;
 use dotenv::dotenv;
+use goosy_vault::state::{MintConfig, MintStats, ProgramInfo, Vault};
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::packet::PACKET_DATA_SIZE;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
-use spl_associated_token_account::{get_associated_token_address, instruction};
-use spl_token::id;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::collections::BTreeMap;
 use std::env;
 use std::error::Error;
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const PROGRAM_ID: &str = "5wsNMDzsM3RepTN9Z2A4DCJViqXE8o3KbFJfa3t5hmZh";
 const PROGRAM_INFO_SEED: &[u8] = b"program_info";
 
+// Accounts fetched per `getMultipleAccounts` call, the RPC limit
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+// Compute budget requested for each batch, and a conservative estimate of a single payout
+const BATCH_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+const COMPUTE_UNITS_PER_VAULT: u32 = 40_000;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
@@ -36,13 +46,26 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let program_id = Pubkey::from_str(PROGRAM_ID)?;
     let (program_info_pda, _) = Pubkey::find_program_address(&[PROGRAM_INFO_SEED], &program_id);
 
-    // Get the number of vaults and process them
-    let vaults_count = fetch_vaults_count(&rpc_client, &program_info_pda).await?;
-    process_vaults(
+    // Nothing can be distributed while the program is paused, skip this run
+    let program_info = fetch_program_info(&rpc_client, &program_info_pda).await?;
+    if program_info.paused {
+        println!("Program is paused, skipping interest accrual");
+        return Ok(());
+    }
+
+    println!(
+        "Vaults: {} ({} active)",
+        program_info.vaults_count, program_info.active_vaults_count
+    );
+
+    // Settle the vaults that are due, packing as many as fit into each transaction
+    let vaults = fetch_vaults_with_shares(&rpc_client, &program_id, &program_info).await?;
+    distribute_interest_to_vaults(
         &rpc_client,
-        vaults_count,
+        vaults,
         &program_id,
         &program_info_pda,
+        &program_info.admin,
         &fee_payer_keypair,
     )
     .await?;
@@ -55,48 +78,56 @@ fn initialize_rpc_client() -> RpcClient {
     RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed())
 }
 
-async fn fetch_vaults_count(
+async fn fetch_program_info(
     rpc_client: &RpcClient,
     program_info_pda: &Pubkey,
-) -> Result<u32, Box<dyn Error>> {
+) -> Result<ProgramInfo, Box<dyn Error>> {
     let program_info_data = rpc_client.get_account_data(program_info_pda).await?;
-    let vaults_count =
-        goosy_vault::state::ProgramInfo::try_deserialize(&mut program_info_data.as_slice())?
-            .vaults_count;
-    Ok(vaults_count)
+    let program_info = ProgramInfo::try_deserialize(&mut program_info_data.as_slice())?;
+    Ok(program_info)
 }
 
-async fn process_vaults(
+/// Fetch the numbered vaults that hold shares. Owner seeded vaults are not numbered, they
+/// collect their interest whenever they are touched.
+async fn fetch_vaults_with_shares(
     rpc_client: &RpcClient,
-    vaults_count: u32,
     program_id: &Pubkey,
-    program_info_pda: &Pubkey,
-    payer: &Keypair,
-) -> Result<(), Box<dyn Error>> {
-    for i in 0..vaults_count {
-        let vault_pda = derive_vault_pda(i, program_id)?;
-
-        let vault_data = rpc_client.get_account_data(&vault_pda).await?;
-        let vault = goosy_vault::state::Vault::try_deserialize(&mut vault_data.as_slice())?;
-
-        println!("Vault index{}: {:?}", i, vault);
-
-        distribute_interest_to_vaults(
-            rpc_client,
-            vec![vault_pda],
-            program_id,
-            program_info_pda,
-            payer,
-        )
-        .await?;
+    program_info: &ProgramInfo,
+) -> Result<Vec<(Pubkey, Vault)>, Box<dyn Error>> {
+    let vault_pdas: Vec<Pubkey> = (0..program_info.vaults_count)
+        .map(|vault_index| derive_vault_pda(vault_index, program_id))
+        .collect();
+
+    let mut vaults = Vec::new();
+    for vault_pdas in vault_pdas.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let accounts = rpc_client.get_multiple_accounts(vault_pdas).await?;
+        for (vault_pda, account) in vault_pdas.iter().zip(accounts) {
+            // Closed vaults leave their index behind
+            let Some(account) = account else {
+                continue;
+            };
+
+            // Baseline vaults cannot be read until the admin has run `migrate_vault` on them
+            let vault = match Vault::try_deserialize(&mut account.data.as_slice()) {
+                Ok(vault) if vault.version > 0 => vault,
+                _ => {
+                    println!("Skipping vault {} awaiting migration", vault_pda);
+                    continue;
+                }
+            };
+            if vault.shares > 0 {
+                vaults.push((*vault_pda, vault));
+            }
+        }
     }
-    Ok(())
+
+    Ok(vaults)
 }
 
-fn derive_vault_pda(vault_index: u32, program_id: &Pubkey) -> Result<Pubkey, Box<dyn Error>> {
+fn derive_vault_pda(vault_index: u32, program_id: &Pubkey) -> Pubkey {
     let vault_index_bytes: &[u8] = &vault_index.to_be_bytes();
     let seeds: &[&[u8]] = &[b"vault", vault_index_bytes];
-    Ok(Pubkey::find_program_address(seeds, program_id).0)
+    Pubkey::find_program_address(seeds, program_id).0
 }
 
 pub fn compute_anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
@@ -112,143 +143,275 @@ pub fn compute_anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
     discriminator
 }
 
-async fn distribute_interest_to_vaults(
+async fn accrue_interest(
     rpc_client: &RpcClient,
-    vaults: Vec<Pubkey>,
     program_id: &Pubkey,
-    program_info_pda: &Pubkey,
+    mint: &Pubkey,
+    mint_config_pda: &Pubkey,
     payer: &Keypair,
 ) -> Result<(), Box<dyn Error>> {
-    let seeds: &[&[u8]] = &[b"admin-vault"];
-    let (admin_vault_pda, _) = Pubkey::find_program_address(seeds, program_id);
+    let accrue_instruction = create_accrue_interest_instruction(program_id, mint, mint_config_pda);
 
-    let mint_key = env::var("MINT_PUBKEY").expect("MINT_PUBKEY must be set");
-    let mint = Pubkey::from_str(&mint_key)?;
+    let mut transaction = Transaction::new_with_payer(&[accrue_instruction], Some(&payer.pubkey()));
+    transaction.sign(&[payer], rpc_client.get_latest_blockhash().await?);
 
-    let admin_vault_spl_token_account =
-        get_or_create_associated_token_account(rpc_client, payer, &mint, &admin_vault_pda).await?;
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await?;
 
-    println!(
-        "Payer public key: {}, admin vault PDA {}",
-        payer.pubkey(),
-        admin_vault_pda
-    );
+    Ok(())
+}
+
+fn create_accrue_interest_instruction(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_config_pda: &Pubkey,
+) -> Instruction {
+    let discriminator = compute_anchor_discriminator("global", "accrue_interest");
+
+    let data = Vec::from(discriminator);
 
-    for vault_pda in vaults {
-        println!("Vault PDA: {}", vault_pda);
-        let destination_vault_spl_associated_token_account =
-            get_or_create_associated_token_account(rpc_client, payer, &mint, &vault_pda).await?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*mint_config_pda, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
 
-        let interest_instruction = create_distribute_interest_instruction(
+async fn distribute_interest_to_vaults(
+    rpc_client: &RpcClient,
+    vaults: Vec<(Pubkey, Vault)>,
+    program_id: &Pubkey,
+    program_info_pda: &Pubkey,
+    admin: &Pubkey,
+    payer: &Keypair,
+) -> Result<(), Box<dyn Error>> {
+    let current_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    // Each batch pays out of a single admin vault, so vaults are grouped by mint
+    let mut vaults_by_mint: BTreeMap<Pubkey, Vec<(Pubkey, Vault)>> = BTreeMap::new();
+    for (vault_pda, vault) in vaults {
+        vaults_by_mint
+            .entry(vault.spl_token_mint)
+            .or_default()
+            .push((vault_pda, vault));
+    }
+
+    let max_vaults_per_batch = (BATCH_COMPUTE_UNIT_LIMIT / COMPUTE_UNITS_PER_VAULT) as usize;
+
+    for (mint, vaults) in vaults_by_mint {
+        // Every mint accrues its own interest index, at its own rate and period
+        let (mint_config_pda, _) =
+            Pubkey::find_program_address(&[b"mint-config", mint.as_ref()], program_id);
+        accrue_interest(rpc_client, program_id, &mint, &mint_config_pda, payer).await?;
+        let mint_config_data = rpc_client.get_account_data(&mint_config_pda).await?;
+        let mint_config = MintConfig::try_deserialize(&mut mint_config_data.as_slice())?;
+
+        let vaults: Vec<(Pubkey, Pubkey)> = vaults
+            .into_iter()
+            .filter(|(_, vault)| vault.is_interest_due(current_time, mint_config.accrual_period))
+            .map(|(vault_pda, vault)| (vault_pda, vault.spl_token_account))
+            .collect();
+        println!(
+            "Interest index of mint {}: {}, {} vaults are due for interest",
+            mint,
+            mint_config.interest_index,
+            vaults.len()
+        );
+        if vaults.is_empty() {
+            continue;
+        }
+
+        // The mint's owner tells Token from Token-2022
+        let token_program = rpc_client.get_account(&mint).await?.owner;
+        let (admin_vault_pda, _) =
+            Pubkey::find_program_address(&[b"admin-vault", mint.as_ref()], program_id);
+        let admin_vault_spl_token_account =
+            get_associated_token_address_with_program_id(&admin_vault_pda, &mint, &token_program);
+        let (mint_stats_pda, _) =
+            Pubkey::find_program_address(&[b"mint-stats", mint.as_ref()], program_id);
+
+        let accounts = BatchAccounts {
             program_id,
-            &admin_vault_pda,
-            &vault_pda,
-            &admin_vault_spl_token_account,
-            &destination_vault_spl_associated_token_account,
-            &mint,
+            admin_vault_pda: &admin_vault_pda,
+            admin_vault_spl_token_account: &admin_vault_spl_token_account,
+            mint: &mint,
+            mint_config_pda: &mint_config_pda,
+            mint_stats_pda: &mint_stats_pda,
             program_info_pda,
-            &id(),
-        );
-        let mut transaction =
-            Transaction::new_with_payer(&[interest_instruction], Some(&payer.pubkey()));
-        transaction.sign(&[payer], rpc_client.get_latest_blockhash().await?);
+            token_program: &token_program,
+        };
+
+        // Snapshot the reserve at the start of each period so every vault gets its pro-rata
+        // share when it cannot cover them all, instead of the first vaults draining it. Only
+        // the admin can start a round, without one the reserve pays vaults in turn.
+        let mint_stats_data = rpc_client.get_account_data(&mint_stats_pda).await?;
+        let mint_stats = MintStats::try_deserialize(&mut mint_stats_data.as_slice())?;
+        if !mint_stats.is_round_active(current_time, mint_config.accrual_period) {
+            if payer.pubkey() == *admin {
+                start_interest_round(rpc_client, &accounts, payer).await?;
+            } else {
+                println!(
+                    "Fee payer is not the admin, no interest round started for mint {}",
+                    mint
+                );
+            }
+        }
 
-        rpc_client
-            .send_and_confirm_transaction(&transaction)
-            .await
-            .expect("TODO: panic message");
+        let mut batch: Vec<(Pubkey, Pubkey)> = Vec::new();
+        for vault in vaults {
+            batch.push(vault);
+            if batch.len() > max_vaults_per_batch || !batch_fits(&accounts, &batch, payer) {
+                let overflow = batch.pop().expect("batch is not empty");
+                send_batch(rpc_client, &accounts, &batch, payer).await?;
+                batch = vec![overflow];
+            }
+        }
+        if !batch.is_empty() {
+            send_batch(rpc_client, &accounts, &batch, payer).await?;
+        }
 
         let admin_vault_spl_token_account_balance = rpc_client
             .get_token_account_balance(&admin_vault_spl_token_account)
             .await?;
-
-        let destination_vault_spl_token_account_balance = rpc_client
-            .get_token_account_balance(&destination_vault_spl_associated_token_account)
-            .await?;
-
-        println!(
-            "Admin vault balance: {:?}",
-            admin_vault_spl_token_account_balance.ui_amount
-        );
         println!(
-            "Destination vault balance: {:?}",
-            destination_vault_spl_token_account_balance.ui_amount
-        );
-        println!(
-            "Destination vault: {:?}",
-            destination_vault_spl_associated_token_account
+            "Admin vault balance for mint {}: {:?}",
+            mint, admin_vault_spl_token_account_balance.ui_amount
         );
     }
 
     Ok(())
 }
 
-fn create_distribute_interest_instruction(
-    program_id: &Pubkey,
-    admin_vault_pda: &Pubkey,
-    destination_vault_pda: &Pubkey,
-    admin_vault_spl_token_account: &Pubkey,
-    destination_vault_spl_token_account: &Pubkey,
-    mint: &Pubkey,
-    program_info_pda: &Pubkey,
-    token_program: &Pubkey,
+/// Accounts shared by every batch of one mint.
+struct BatchAccounts<'a> {
+    program_id: &'a Pubkey,
+    admin_vault_pda: &'a Pubkey,
+    admin_vault_spl_token_account: &'a Pubkey,
+    mint: &'a Pubkey,
+    mint_config_pda: &'a Pubkey,
+    mint_stats_pda: &'a Pubkey,
+    program_info_pda: &'a Pubkey,
+    token_program: &'a Pubkey,
+}
+
+async fn start_interest_round(
+    rpc_client: &RpcClient,
+    accounts: &BatchAccounts<'_>,
+    payer: &Keypair,
+) -> Result<(), Box<dyn Error>> {
+    let round_instruction = create_start_interest_round_instruction(accounts, &payer.pubkey());
+
+    let mut transaction = Transaction::new_with_payer(&[round_instruction], Some(&payer.pubkey()));
+    transaction.sign(&[payer], rpc_client.get_latest_blockhash().await?);
+
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await?;
+    println!("Started an interest round for mint {}", accounts.mint);
+
+    Ok(())
+}
+
+fn create_start_interest_round_instruction(
+    accounts: &BatchAccounts,
+    admin: &Pubkey,
 ) -> Instruction {
-    let discriminator = compute_anchor_discriminator("global", "distribute_interest"); // [161, 80, 239, 247, 115, 254, 122, 80]
+    let discriminator = compute_anchor_discriminator("global", "start_interest_round");
 
     let data = Vec::from(discriminator);
 
-    let accounts = vec![
-        AccountMeta::new(*admin_vault_pda, false),
-        AccountMeta::new(*destination_vault_pda, false),
-        AccountMeta::new(*admin_vault_spl_token_account, false),
-        AccountMeta::new(*destination_vault_spl_token_account, false),
-        AccountMeta::new_readonly(*mint, false),
-        AccountMeta::new_readonly(*program_info_pda, false),
-        AccountMeta::new_readonly(*token_program, false),
+    let account_metas = vec![
+        AccountMeta::new_readonly(*accounts.admin_vault_pda, false),
+        AccountMeta::new_readonly(*accounts.admin_vault_spl_token_account, false),
+        AccountMeta::new_readonly(*accounts.mint, false),
+        AccountMeta::new(*accounts.mint_config_pda, false),
+        AccountMeta::new(*accounts.mint_stats_pda, false),
+        AccountMeta::new_readonly(*accounts.program_info_pda, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(*accounts.token_program, false),
     ];
 
     Instruction {
-        program_id: *program_id,
-        accounts,
+        program_id: *accounts.program_id,
+        accounts: account_metas,
         data,
     }
 }
 
-async fn get_or_create_associated_token_account(
+fn create_batch_transaction(
+    accounts: &BatchAccounts,
+    vaults: &[(Pubkey, Pubkey)],
+    payer: &Keypair,
+) -> Transaction {
+    let instructions = [
+        ComputeBudgetInstruction::set_compute_unit_limit(BATCH_COMPUTE_UNIT_LIMIT),
+        create_distribute_interest_batch_instruction(accounts, vaults),
+    ];
+    Transaction::new_with_payer(&instructions, Some(&payer.pubkey()))
+}
+
+fn batch_fits(accounts: &BatchAccounts, vaults: &[(Pubkey, Pubkey)], payer: &Keypair) -> bool {
+    let transaction = create_batch_transaction(accounts, vaults, payer);
+
+    // A signed transaction is its signatures, prefixed by their count, and the message
+    let signatures = transaction.message.header.num_required_signatures as usize;
+    let size = 1 + signatures * 64 + transaction.message_data().len();
+    size <= PACKET_DATA_SIZE
+}
+
+async fn send_batch(
     rpc_client: &RpcClient,
+    accounts: &BatchAccounts<'_>,
+    vaults: &[(Pubkey, Pubkey)],
     payer: &Keypair,
-    mint: &Pubkey,
-    owner: &Pubkey,
-) -> Result<Pubkey, Box<dyn Error>> {
-    let associated_token_address = get_associated_token_address(owner, mint);
-
-    match rpc_client.get_account(&associated_token_address).await {
-        Ok(_) => {
-            println!(
-                "Associated token account exists: {}",
-                associated_token_address
-            );
-            Ok(associated_token_address)
-        }
-        Err(_) => {
-            // Create the associated token account
-            let create_account_instruction =
-                instruction::create_associated_token_account(&payer.pubkey(), owner, mint, &id());
-
-            let mut transaction =
-                Transaction::new_with_payer(&[create_account_instruction], Some(&payer.pubkey()));
-            let recent_blockhash = rpc_client.get_latest_blockhash().await?;
-            transaction.sign(&[payer], recent_blockhash);
-            rpc_client
-                .send_and_confirm_transaction(&transaction)
-                .await?;
-
-            println!(
-                "Associated token account created: {}",
-                associated_token_address
-            );
-
-            Ok(associated_token_address)
-        }
+) -> Result<(), Box<dyn Error>> {
+    let mut transaction = create_batch_transaction(accounts, vaults, payer);
+    transaction.sign(&[payer], rpc_client.get_latest_blockhash().await?);
+
+    let signature = rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await?;
+    println!(
+        "Distributed interest to {} vaults: {}",
+        vaults.len(),
+        signature
+    );
+
+    Ok(())
+}
+
+fn create_distribute_interest_batch_instruction(
+    accounts: &BatchAccounts,
+    vaults: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let discriminator = compute_anchor_discriminator("global", "distribute_interest_batch");
+
+    let data = Vec::from(discriminator);
+
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(*accounts.admin_vault_pda, false),
+        AccountMeta::new(*accounts.admin_vault_spl_token_account, false),
+        AccountMeta::new_readonly(*accounts.mint, false),
+        AccountMeta::new(*accounts.mint_config_pda, false),
+        AccountMeta::new(*accounts.mint_stats_pda, false),
+        AccountMeta::new_readonly(*accounts.program_info_pda, false),
+        AccountMeta::new_readonly(*accounts.token_program, false),
+    ];
+    // (vault, vault token account) pairs
+    for (vault_pda, vault_spl_token_account) in vaults {
+        account_metas.push(AccountMeta::new(*vault_pda, false));
+        account_metas.push(AccountMeta::new(*vault_spl_token_account, false));
+    }
+
+    Instruction {
+        program_id: *accounts.program_id,
+        accounts: account_metas,
+        data,
     }
 }
@@ -8,6 +8,88 @@ pub enum VaultError {
     InvalidSPLTokenType,
     #[msg("Action not permitted. The vault owner is not the signer of the transaction.")]
     InvalidVaultOwner,
-    #[msg("Interest has not been accrued yet. Please try again after a month.")]
+    #[msg("Interest has not been accrued yet. Please try again after the accrual period.")]
     InterestNotAccruedYet,
+    #[msg("The interest rate or accrual period is out of the allowed range.")]
+    InvalidInterestParams,
+    #[msg("The vault balance would overflow.")]
+    BalanceOverflow,
+    #[msg("The vault balance would underflow.")]
+    BalanceUnderflow,
+    #[msg("An arithmetic operation overflowed.")]
+    MathOverflow,
+    #[msg("Only the program's upgrade authority can initialise the program info.")]
+    NotUpgradeAuthority,
+    #[msg("There is no pending admin to accept.")]
+    NoPendingAdmin,
+    #[msg("The signer is not the pending admin.")]
+    NotPendingAdmin,
+    #[msg("Minting this amount would exceed the mint cap for the current epoch.")]
+    MintCapExceeded,
+    #[msg("The program is paused.")]
+    ProgramPaused,
+    #[msg("Withdrawals are paused.")]
+    WithdrawalsPaused,
+    #[msg("The vault is locked and early withdrawals are not allowed.")]
+    VaultLocked,
+    #[msg("The lock duration is out of the allowed range.")]
+    InvalidLockDuration,
+    #[msg("The early withdrawal penalty or locked interest bonus is out of the allowed range.")]
+    InvalidLockParams,
+    #[msg("The vault still holds funds, withdraw them before closing it.")]
+    VaultNotEmpty,
+    #[msg("The co-signers or signature threshold are invalid.")]
+    InvalidVaultAuthority,
+    #[msg("The vault authority account is missing or does not belong to the vault.")]
+    MissingVaultAuthority,
+    #[msg("Not enough of the vault's owners signed the transaction.")]
+    NotEnoughSigners,
+    #[msg("The signer is neither the vault owner nor one of its delegates.")]
+    UnauthorizedDelegate,
+    #[msg("The withdrawal exceeds the delegate's daily limit.")]
+    DelegateLimitExceeded,
+    #[msg("The vault already has the maximum number of delegates.")]
+    TooManyDelegates,
+    #[msg("The batch has to be made of (vault, vault token account) pairs of the batch's mint.")]
+    InvalidBatchAccounts,
+    #[msg("The token account is not the one recorded on the vault.")]
+    InvalidVaultTokenAccount,
+    #[msg("The token account's mint does not match the vault's mint.")]
+    InvalidTokenAccountMint,
+    #[msg("The token account is not owned by the vault.")]
+    InvalidTokenAccountAuthority,
+    #[msg("An interest round is already in progress for this mint.")]
+    InterestRoundInProgress,
+    #[msg("The deposit memo is too long.")]
+    MemoTooLong,
+    #[msg("The account is already at the current layout version.")]
+    AlreadyMigrated,
+    #[msg("The deposit or withdrawal fee is above the allowed maximum.")]
+    InvalidFeeParams,
+    #[msg("The token account is not the mint's treasury.")]
+    InvalidTreasury,
+    #[msg("There are no fees to collect.")]
+    NoFeesToCollect,
+    #[msg("The minimum deposit is above one of the maximum deposit caps.")]
+    InvalidDepositCaps,
+    #[msg("The deposit is below the minimum deposit.")]
+    DepositTooSmall,
+    #[msg("The deposit would take the vault above its maximum balance.")]
+    VaultBalanceCapExceeded,
+    #[msg("The deposit would take the mint above its maximum total deposits.")]
+    MintDepositCapExceeded,
+    #[msg("The vault is not the reserve vault registered for the mint.")]
+    InvalidReserveVault,
+    #[msg("New vaults cannot be created for this mint.")]
+    VaultsDisabled,
+    #[msg("Deposits into this mint's vaults are disabled.")]
+    DepositsDisabled,
+    #[msg("Tokens cannot be transferred to the vault they come from.")]
+    SameVault,
+    #[msg("The legacy reserve holds nothing to sweep.")]
+    LegacyReserveEmpty,
+    #[msg("The vault already holds its balance in its own token account.")]
+    AlreadyInCustody,
+    #[msg("The vault has interest pending, have it paid out before closing the vault.")]
+    InterestPending,
 }
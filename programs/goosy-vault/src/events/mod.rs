@@ -0,0 +1,3 @@
+mod vault_events;
+
+pub use vault_events::*;
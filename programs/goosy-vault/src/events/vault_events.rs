@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VaultCreated {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub spl_token_account: Pubkey,
+    pub vault_num: u32,
+    pub unlock_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault_num: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultAuthorityUpdated {
+    pub vault: Pubkey,
+    pub co_signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub multisig_threshold_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateUpdated {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub daily_withdrawal_limit: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Deposited {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub gross_amount: u64,
+    pub fee: u64,
+    pub amount: u64,
+    pub memo: Option<String>,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub fee: u64,
+    pub net_amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferredBetweenVaults {
+    pub source_vault: Pubkey,
+    pub destination_vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub received_amount: u64,
+    pub source_balance: u64,
+    pub destination_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestDistributed {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub shortfall: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensMinted {
+    pub admin_vault: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockParamsUpdated {
+    pub early_withdrawal_penalty_bps: u16,
+    pub locked_interest_bonus_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub paused: bool,
+    pub withdrawals_paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintCapUpdated {
+    pub mint: Pubkey,
+    pub mint_cap_per_epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestParamsUpdated {
+    pub mint: Pubkey,
+    pub interest_rate_bps: u16,
+    pub accrual_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestRoundStarted {
+    pub mint: Pubkey,
+    pub reserve: u64,
+    pub interest_owed: u64,
+    pub payout_ratio: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestIndexAccrued {
+    pub mint: Pubkey,
+    pub interest_index: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeParamsUpdated {
+    pub mint: Pubkey,
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesCollected {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LegacyReserveSwept {
+    pub mint: Pubkey,
+    pub admin_vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositCapsUpdated {
+    pub mint: Pubkey,
+    pub max_vault_balance: u64,
+    pub max_total_deposits: u64,
+    pub min_deposit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintRegistered {
+    pub mint: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub decimals: u8,
+    pub interest_rate_bps: u16,
+    pub accrual_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintEnabledUpdated {
+    pub mint: Pubkey,
+    pub vaults_enabled: bool,
+    pub deposits_enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultCustodyTaken {
+    pub vault: Pubkey,
+    pub spl_token_account: Pubkey,
+    pub balance: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
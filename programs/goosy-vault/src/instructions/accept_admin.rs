@@ -0,0 +1,30 @@
+use crate::errors::VaultError;
+use crate::events::AdminChanged;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = program_info.pending_admin.is_some() @ VaultError::NoPendingAdmin,
+        constraint = program_info.pending_admin == Some(new_admin.key()) @ VaultError::NotPendingAdmin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    new_admin: Signer<'info>,
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let previous_admin = ctx.accounts.program_info.accept_admin()?;
+
+    emit!(AdminChanged {
+        previous_admin,
+        new_admin: ctx.accounts.program_info.admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::events::InterestIndexAccrued;
+use crate::state::MintConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct AccrueInterest<'info> {
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.accrue_interest(current_time)?;
+
+    msg!(
+        "Interest index of mint {} accrued to: {}",
+        mint_config.mint,
+        mint_config.interest_index
+    );
+
+    emit!(InterestIndexAccrued {
+        mint: mint_config.mint,
+        interest_index: mint_config.interest_index,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::errors::VaultError;
+use crate::events::VaultClosed;
+use crate::instructions::transfer_from_vault;
+use crate::state::{MintConfig, MintStats, ProgramInfo, Vault, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{CloseAccount, Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    // Only user vaults can be closed, admin vaults are derived from different seeds
+    #[account(
+        mut,
+        constraint = vault.is_user_vault_address(&vault.key(), &crate::ID),
+        has_one = owner @ VaultError::InvalidVaultOwner,
+        close = owner,
+    )]
+    vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_spl_token_account.key() == vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = vault_spl_token_account.mint == vault.spl_token_mint
+            @ VaultError::InvalidTokenAccountMint,
+        constraint = vault_spl_token_account.owner == vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Receives tokens sent to the vault's token account without a deposit
+    #[account(mut, token::mint = mint)]
+    user_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VaultAuthority::SEED, vault.key().as_ref()],
+        bump = vault_authority.bump,
+        close = owner,
+    )]
+    vault_authority: Option<Account<'info, VaultAuthority>>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(constraint = mint.key() == vault.spl_token_mint @ VaultError::InvalidSPLTokenType)]
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(mut, seeds = [ProgramInfo::SEED.as_bytes()], bump = program_info.bump)]
+    program_info: Account<'info, ProgramInfo>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+    // the vault can only be closed once everything has been withdrawn
+    if ctx.accounts.vault.total_balance() != 0 {
+        return Err(VaultError::VaultNotEmpty.into());
+    }
+
+    // the vault authority is closed along with the vault
+    if ctx.accounts.vault.has_vault_authority != ctx.accounts.vault_authority.is_some() {
+        return Err(VaultError::MissingVaultAuthority.into());
+    }
+
+    // Shares still worth a token are an unpaid claim on the reserve, including any shortfall
+    // a round could not cover, and would be lost with the vault
+    ctx.accounts
+        .mint_config
+        .accrue_interest(Clock::get()?.unix_timestamp)?;
+    if ctx
+        .accounts
+        .vault
+        .pending_interest(&ctx.accounts.mint_config)?
+        > 0
+    {
+        return Err(VaultError::InterestPending.into());
+    }
+
+    let vault = &ctx.accounts.vault;
+
+    // Anyone can send tokens to the vault's token account, hand them to the owner so they do
+    // not stop it from being closed
+    let unrecorded_amount = ctx.accounts.vault_spl_token_account.amount;
+    if unrecorded_amount > 0 {
+        transfer_from_vault(
+            vault,
+            &ctx.accounts.vault_spl_token_account,
+            ctx.accounts.user_spl_token_account.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            unrecorded_amount,
+        )?;
+    }
+
+    // Only rounding dust worth less than a token is left in shares, it is dropped with the vault
+    ctx.accounts.mint_stats.record_withdrawal(0, vault.shares)?;
+
+    // Close the vault's token account, returning its rent to the owner
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault_spl_token_account.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: vault.to_account_info(), // PDA as authority
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    vault.with_signer_seeds(|signer_seeds| {
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::close_account(cpi_ctx)
+    })?;
+
+    // The vault account itself is closed by Anchor once the instruction succeeds. Owner
+    // seeded vaults are not counted, they are created without touching the program info.
+    if !vault.owner_seeded {
+        ctx.accounts.program_info.decrement_active_vaults_count()?;
+    }
+
+    msg!("Vault {} closed", vault.vault_num);
+
+    emit!(VaultClosed {
+        vault: vault.key(),
+        owner: vault.owner,
+        mint: vault.spl_token_mint,
+        vault_num: vault.vault_num,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
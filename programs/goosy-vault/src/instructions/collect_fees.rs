@@ -0,0 +1,70 @@
+use crate::errors::VaultError;
+use crate::events::FeesCollected;
+use crate::state::{MintStats, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_stats,
+        associated_token::token_program = token_program,
+    )]
+    fee_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_spl_token_account.key() == mint_stats.treasury
+            @ VaultError::InvalidTreasury,
+    )]
+    treasury_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+    let amount = ctx.accounts.mint_stats.collect_fees()?;
+    if amount == 0 {
+        return Err(VaultError::NoFeesToCollect.into());
+    }
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.fee_spl_token_account.to_account_info(),
+        to: ctx.accounts.treasury_spl_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.mint_stats.to_account_info(), // PDA as authority
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    ctx.accounts.mint_stats.with_signer_seeds(|signer_seeds| {
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)
+    })?;
+
+    msg!("Fees collected into the treasury: {}", amount);
+
+    emit!(FeesCollected {
+        mint: ctx.accounts.mint.key(),
+        treasury: ctx.accounts.treasury_spl_token_account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
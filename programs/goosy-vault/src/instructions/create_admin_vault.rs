@@ -1,38 +1,63 @@
+use crate::errors::VaultError;
+use crate::events::VaultCreated;
 use crate::state::{ProgramInfo, Vault};
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 #[derive(Accounts)]
 pub struct CreateAdminVault<'info> {
     #[account(
     init,
-    payer = owner,
-    seeds = [b"admin-vault"],
+    payer = admin,
+    seeds = [Vault::ADMIN_SEED, mint.key().as_ref()],
     bump,
     space = 8 + Vault::MAXIMUM_SPACE
     )]
     vault: Account<'info, Vault>,
 
     #[account(mut)]
-    owner: Signer<'info>,
+    admin: Signer<'info>,
 
-    #[account(mut, seeds = [ProgramInfo::SEED.as_bytes().as_ref()], bump = program_info.bump)]
+    // The reserve's address is predictable, only the admin may create it
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes().as_ref()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
     program_info: Account<'info, ProgramInfo>,
 
-    spl_token_account: Account<'info, TokenAccount>,
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        token::mint = mint,
+        constraint = spl_token_account.owner == vault.key() @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    spl_token_account: InterfaceAccount<'info, TokenAccount>,
 
     system_program: Program<'info, System>,
 }
 
 pub fn create_admin_vault(ctx: Context<CreateAdminVault>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
     ctx.accounts.vault.set_inner(Vault::new(
         ctx.bumps.vault,
-        ctx.accounts.owner.key(),
+        ctx.accounts.admin.key(),
         ctx.accounts.program_info.vaults_count,
         ctx.accounts.spl_token_account.key(),
         ctx.accounts.spl_token_account.mint,
-        Clock::get()?.unix_timestamp,
+        ctx.accounts.mint.decimals,
+        current_time,
     ));
 
+    emit!(VaultCreated {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        mint: ctx.accounts.vault.spl_token_mint,
+        spl_token_account: ctx.accounts.vault.spl_token_account,
+        vault_num: ctx.accounts.vault.vault_num,
+        unlock_at: ctx.accounts.vault.unlock_at,
+        timestamp: current_time,
+    });
+
     Ok(())
 }
@@ -0,0 +1,83 @@
+use crate::errors::VaultError;
+use crate::events::VaultCreated;
+use crate::state::{MintConfig, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Owner seeded vaults can be derived by clients from the owner, mint and nonce, and creating
+// one does not write to the program info, so concurrent creations do not contend on it.
+#[derive(Accounts)]
+#[instruction(nonce: u32)]
+pub struct CreateOwnerVault<'info> {
+    #[account(
+    init,
+    payer = owner,
+    seeds = [Vault::OWNER_SEED, owner.key().as_ref(), mint.key().as_ref(), nonce.to_be_bytes().as_ref()],
+    bump,
+    space = 8 + Vault::MAXIMUM_SPACE
+    )]
+    vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.vaults_enabled @ VaultError::VaultsDisabled,
+    )]
+    mint_config: Account<'info, MintConfig>,
+
+    // The vault's token account is owned by the vault PDA so only the program can move funds.
+    // Its address can be derived before the vault exists, so someone may have created it first.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+pub fn create_owner_vault(
+    ctx: Context<CreateOwnerVault>,
+    nonce: u32,
+    lock_duration: Option<i64>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.vault.set_inner(Vault::new(
+        ctx.bumps.vault,
+        ctx.accounts.owner.key(),
+        nonce,
+        ctx.accounts.spl_token_account.key(),
+        ctx.accounts.spl_token_account.mint,
+        ctx.accounts.mint_config.decimals,
+        current_time,
+    ));
+    ctx.accounts.vault.owner_seeded = true;
+
+    // Term deposits can only be withdrawn early against a penalty
+    if let Some(lock_duration) = lock_duration {
+        ctx.accounts.vault.lock(lock_duration)?;
+    }
+
+    emit!(VaultCreated {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        mint: ctx.accounts.vault.spl_token_mint,
+        spl_token_account: ctx.accounts.vault.spl_token_account,
+        vault_num: ctx.accounts.vault.vault_num,
+        unlock_at: ctx.accounts.vault.unlock_at,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
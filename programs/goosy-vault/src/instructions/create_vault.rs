@@ -1,13 +1,16 @@
-use crate::state::{ProgramInfo, Vault};
+use crate::errors::VaultError;
+use crate::events::VaultCreated;
+use crate::state::{MintConfig, ProgramInfo, Vault};
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct CreateVault<'info> {
     #[account(
     init,
     payer = owner,
-    seeds = [b"vault", program_info.vaults_count.to_be_bytes().as_ref()],
+    seeds = [Vault::SEED, program_info.vaults_count.to_be_bytes().as_ref()],
     bump,
     space = 8 + Vault::MAXIMUM_SPACE
     )]
@@ -19,23 +22,60 @@ pub struct CreateVault<'info> {
     #[account(mut, seeds = [ProgramInfo::SEED.as_bytes().as_ref()], bump = program_info.bump)]
     program_info: Account<'info, ProgramInfo>,
 
-    spl_token_account: Account<'info, TokenAccount>,
+    mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.vaults_enabled @ VaultError::VaultsDisabled,
+    )]
+    mint_config: Account<'info, MintConfig>,
+
+    // The vault's token account is owned by the vault PDA so only the program can move funds.
+    // Its address can be derived before the vault exists, so someone may have created it first.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
 }
 
-pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+pub fn create_vault(ctx: Context<CreateVault>, lock_duration: Option<i64>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
     ctx.accounts.vault.set_inner(Vault::new(
         ctx.bumps.vault,
         ctx.accounts.owner.key(),
         ctx.accounts.program_info.vaults_count,
         ctx.accounts.spl_token_account.key(),
         ctx.accounts.spl_token_account.mint,
-        Clock::get()?.unix_timestamp,
+        ctx.accounts.mint_config.decimals,
+        current_time,
     ));
 
+    // Term deposits can only be withdrawn early against a penalty
+    if let Some(lock_duration) = lock_duration {
+        ctx.accounts.vault.lock(lock_duration)?;
+    }
+
     // Increment the number of vaults by one.
-    ctx.accounts.program_info.increment_vaults_count();
+    ctx.accounts.program_info.increment_vaults_count()?;
+
+    emit!(VaultCreated {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        mint: ctx.accounts.vault.spl_token_mint,
+        spl_token_account: ctx.accounts.vault.spl_token_account,
+        vault_num: ctx.accounts.vault.vault_num,
+        unlock_at: ctx.accounts.vault.unlock_at,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
@@ -0,0 +1,55 @@
+use crate::events::VaultAuthorityUpdated;
+use crate::state::{Vault, VaultAuthority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateVaultAuthority<'info> {
+    #[account(mut, has_one = owner)]
+    vault: Account<'info, Vault>,
+
+    #[account(
+    init,
+    payer = owner,
+    seeds = [VaultAuthority::SEED, vault.key().as_ref()],
+    bump,
+    space = 8 + VaultAuthority::MAXIMUM_SPACE
+    )]
+    vault_authority: Account<'info, VaultAuthority>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn create_vault_authority(
+    ctx: Context<CreateVaultAuthority>,
+    co_signers: Vec<Pubkey>,
+    threshold: u8,
+    multisig_threshold_amount: u64,
+) -> Result<()> {
+    let vault_authority = &mut ctx.accounts.vault_authority;
+    vault_authority.set_inner(VaultAuthority::new(
+        ctx.accounts.vault.key(),
+        ctx.bumps.vault_authority,
+    ));
+    vault_authority.set_multisig(
+        &ctx.accounts.vault.owner,
+        co_signers,
+        threshold,
+        multisig_threshold_amount,
+    )?;
+
+    // Withdrawals have to present the vault authority from now on
+    ctx.accounts.vault.has_vault_authority = true;
+
+    emit!(VaultAuthorityUpdated {
+        vault: ctx.accounts.vault.key(),
+        co_signers: vault_authority.co_signers.clone(),
+        threshold,
+        multisig_threshold_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
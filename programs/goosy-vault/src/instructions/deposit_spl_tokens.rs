@@ -1,38 +1,125 @@
-use crate::state::ProgramInfo;
+use crate::events::Deposited;
+use crate::instructions::{pay_interest, InterestReserve};
+use crate::state::{MintConfig, MintStats, ProgramInfo};
 use crate::{errors::VaultError, state::Vault};
 use anchor_lang::context::{Context, CpiContext};
 use anchor_lang::prelude::*;
 use anchor_lang::{Accounts, ToAccountInfo};
-use anchor_spl::token;
-use anchor_spl::token::{Mint, Token, TokenAccount, TransferChecked};
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
+/// Longest memo a deposit can carry, in bytes.
+pub const MAX_MEMO_LENGTH: usize = 64;
+
+/// The depositor authorises the transfer out of the source token account, as its owner or
+/// delegate, and can deposit into anyone's vault. Whoever pays for the transaction does not
+/// need to be an account of the instruction, the protocol fee is taken from the deposited tokens.
 #[derive(Accounts)]
 pub struct DepositSPLTokens<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = mint_config.verify_spl_token_type(user_vault.spl_token_mint)
+            @ VaultError::InvalidSPLTokenType,
+    )]
     user_vault: Account<'info, Vault>,
     #[account(
         mut,
-        associated_token::mint=mint,
-        associated_token::authority=fee_payer.key(),
+        constraint = user_spl_token_account.mint == mint.key() @ VaultError::InvalidTokenAccountMint,
     )]
-    user_spl_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    destination_vault_spl_token_account: Account<'info, TokenAccount>,
+    user_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination_vault_spl_token_account.key() == user_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = destination_vault_spl_token_account.mint == user_vault.spl_token_mint
+            @ VaultError::InvalidTokenAccountMint,
+        constraint = destination_vault_spl_token_account.owner == user_vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    destination_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account()]
-    mint: Account<'info, Mint>,
-    #[account(signer)]
-    pub fee_payer: Signer<'info>,
-    token_program: Program<'info, Token>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.deposits_enabled @ VaultError::DepositsDisabled,
+    )]
+    mint_config: Account<'info, MintConfig>,
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=mint_stats,
+        associated_token::token_program=token_program,
+    )]
+    fee_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    depositor: Signer<'info>,
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+    #[account(
+        mut,
+        address = mint_config.reserve_vault @ VaultError::InvalidReserveVault,
+        constraint = admin_vault.key() != user_vault.key(),
+    )]
+    admin_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=admin_vault.key(),
+        associated_token::token_program=token_program,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+    )]
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn deposit_spl_tokens(ctx: Context<DepositSPLTokens>, amount: u64) -> Result<()> {
-    let user_vault = &mut ctx.accounts.user_vault;
+pub fn deposit_spl_tokens(
+    ctx: Context<DepositSPLTokens>,
+    amount: u64,
+    memo: Option<String>,
+) -> Result<()> {
+    // the memo is only carried on the event, for integrations to reconcile deposits
+    if matches!(&memo, Some(memo) if memo.len() > MAX_MEMO_LENGTH) {
+        return Err(VaultError::MemoTooLong.into());
+    }
+
+    // A deposit pays out the vault's interest first, an empty one would let anyone do that to
+    // any vault for free
+    if amount == 0 {
+        return Err(VaultError::DepositTooSmall.into());
+    }
 
     // verify if the vault mint matches the mint of the token account
-    if !user_vault.verify_spl_token_type(ctx.accounts.user_spl_token_account.mint) {
+    if !ctx
+        .accounts
+        .mint_config
+        .verify_spl_token_type(ctx.accounts.user_spl_token_account.mint)
+    {
         return Err(VaultError::InvalidSPLTokenType.into());
     }
 
+    // Pay out the interest earned so far before the vault's shares change
+    pay_interest(
+        &ctx.accounts.program_info,
+        &mut ctx.accounts.user_vault,
+        &mut ctx.accounts.destination_vault_spl_token_account,
+        InterestReserve {
+            admin_vault: &ctx.accounts.admin_vault,
+            admin_vault_spl_token_account: &ctx.accounts.admin_vault_spl_token_account,
+            mint_config: &mut ctx.accounts.mint_config,
+            mint_stats: &mut ctx.accounts.mint_stats,
+            mint: &ctx.accounts.mint,
+            token_program: &ctx.accounts.token_program,
+        },
+    )?;
+
     // Log pre-transfer balances
     msg!(
         "User vault balance before transfer: {}",
@@ -43,27 +130,77 @@ pub fn deposit_spl_tokens(ctx: Context<DepositSPLTokens>, amount: u64) -> Result
         ctx.accounts.destination_vault_spl_token_account.amount
     );
 
+    // The protocol fee is taken from the gross amount and held until the admin collects it
+    let fee = ctx.accounts.mint_config.calculate_deposit_fee(amount)?;
+    let fee_received = if fee > 0 {
+        let balance_before_transfer = ctx.accounts.fee_spl_token_account.amount;
+        transfer_from_depositor(
+            ctx.accounts,
+            ctx.accounts.fee_spl_token_account.to_account_info(),
+            fee,
+        )?;
+        ctx.accounts.fee_spl_token_account.reload()?;
+        ctx.accounts
+            .fee_spl_token_account
+            .amount
+            .checked_sub(balance_before_transfer)
+            .ok_or(VaultError::BalanceUnderflow)?
+    } else {
+        0
+    };
+    msg!("Protocol fee taken from deposit: {}", fee);
+
     // Perform SPL token transfer from user to vault
-    let cpi_accounts = TransferChecked {
-        from: ctx.accounts.user_spl_token_account.to_account_info(),
-        to: ctx
-            .accounts
+    transfer_from_depositor(
+        ctx.accounts,
+        ctx.accounts
             .destination_vault_spl_token_account
             .to_account_info(),
-        authority: ctx.accounts.fee_payer.to_account_info(), // PDA as authority
-        mint: ctx.accounts.mint.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer_checked(cpi_ctx, amount, ProgramInfo::TOKEN_DECIMALS)?;
-
-    // Update vault balance
-    user_vault.update_balance_after_deposit(amount);
+        amount - fee,
+    )?;
 
     // Fetch updated accounts post transfer
+    let balance_before_transfer = ctx.accounts.destination_vault_spl_token_account.amount;
     ctx.accounts.user_spl_token_account.reload()?;
     ctx.accounts.destination_vault_spl_token_account.reload()?;
 
+    // Token-2022 transfer fees are withheld from the destination, credit only the net amount
+    let net_amount = ctx
+        .accounts
+        .destination_vault_spl_token_account
+        .amount
+        .checked_sub(balance_before_transfer)
+        .ok_or(VaultError::BalanceUnderflow)?;
+    msg!("Net amount deposited after transfer fees: {}", net_amount);
+
+    // Caps apply to what the vault is credited, after the protocol and transfer fees
+    ctx.accounts.mint_config.verify_deposit_caps(
+        ctx.accounts.mint_stats.total_deposits,
+        ctx.accounts.user_vault.total_balance(),
+        net_amount,
+    )?;
+
+    // Update vault balance and issue shares at the current interest index
+    let user_vault = &mut ctx.accounts.user_vault;
+    user_vault.update_balance_after_deposit(net_amount)?;
+    user_vault.record_fee(fee)?;
+    let shares = user_vault.mint_shares(net_amount, &ctx.accounts.mint_config)?;
+    ctx.accounts.mint_stats.record_deposit(net_amount, shares)?;
+    ctx.accounts.mint_stats.record_fee(fee_received)?;
+
+    emit!(Deposited {
+        vault: user_vault.key(),
+        owner: user_vault.owner,
+        depositor: ctx.accounts.depositor.key(),
+        mint: user_vault.spl_token_mint,
+        gross_amount: amount,
+        fee,
+        amount: net_amount,
+        memo,
+        balance: user_vault.total_balance(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     // Log post-transfer balances
     msg!(
         "User vault balance after transfer: {}",
@@ -76,3 +213,19 @@ pub fn deposit_spl_tokens(ctx: Context<DepositSPLTokens>, amount: u64) -> Result
 
     Ok(())
 }
+
+/// Transfer `amount` out of the depositor's token account, authorised by the depositor.
+fn transfer_from_depositor<'info>(
+    accounts: &DepositSPLTokens<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: accounts.user_spl_token_account.to_account_info(),
+        to,
+        authority: accounts.depositor.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, accounts.mint.decimals)
+}
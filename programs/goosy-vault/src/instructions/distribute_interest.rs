@@ -1,62 +1,76 @@
-use crate::state::ProgramInfo;
+use crate::events::InterestDistributed;
+use crate::state::{MintConfig, MintStats, ProgramInfo};
 use crate::{errors::VaultError, state::Vault};
 use anchor_lang::context::{Context, CpiContext};
 use anchor_lang::prelude::*;
 use anchor_lang::{Accounts, ToAccountInfo};
-use anchor_spl::token;
-use anchor_spl::token::{Mint, Token, TokenAccount, TransferChecked};
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct DistributeInterest<'info> {
-    #[account(mut)]
+    // The mint's registered reserve, it keeps paying out when the admin role is handed over
+    #[account(mut, address = mint_config.reserve_vault @ VaultError::InvalidReserveVault)]
     admin_vault: Account<'info, Vault>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = mint_config.verify_spl_token_type(destination_vault.spl_token_mint)
+            @ VaultError::InvalidSPLTokenType,
+    )]
     destination_vault: Account<'info, Vault>,
 
     #[account(
         mut,
         associated_token::mint=mint,
         associated_token::authority=admin_vault.key(),
+        associated_token::token_program=token_program,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
     )]
-    admin_vault_spl_token_account: Account<'info, TokenAccount>,
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    destination_vault_spl_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination_vault_spl_token_account.key() == destination_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = destination_vault_spl_token_account.mint == destination_vault.spl_token_mint
+            @ VaultError::InvalidTokenAccountMint,
+        constraint = destination_vault_spl_token_account.owner == destination_vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    destination_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(constraint = admin_vault_spl_token_account.mint == mint.key())]
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
-    #[account(constraint = admin_vault.owner == program_info.admin)]
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+    )]
     program_info: Account<'info, ProgramInfo>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn distribute_interest(ctx: Context<DistributeInterest>) -> Result<()> {
-    let admin_vault = &mut ctx.accounts.admin_vault;
-    let destination_vault = &mut ctx.accounts.destination_vault;
-
-    // calculate the interest amount
-    let interest_amount = ctx.accounts.destination_vault_spl_token_account.amount / 100;
-    msg!("Interest amount to be transferred: {}", interest_amount);
-
-    // check if sufficient balance in admin's token account
-    if ctx.accounts.admin_vault_spl_token_account.amount < interest_amount {
-        msg!("Insufficient balance in admin's token account");
-        return Err(VaultError::InsufficientBalance.into());
-    }
-
-    // check if it's not been a month since creation of the vault
+    // check if an accrual period has passed since interest was last paid to the vault
     let current_time = Clock::get()?.unix_timestamp;
-    let one_month = 30 * 24 * 60 * 60; // Approximate number of seconds in a month
-    if current_time - destination_vault.creation_date < one_month {
+    if !ctx
+        .accounts
+        .destination_vault
+        .is_interest_due(current_time, ctx.accounts.mint_config.accrual_period)
+    {
         return Err(VaultError::InterestNotAccruedYet.into());
     }
 
-    let seeds = &[b"admin-vault".as_ref(), &[admin_vault.bump]];
-    let signer_seeds = &[&seeds[..]];
-
     // Log pre-transfer balances
     msg!(
         "Admin vault balance before transfer: {}",
@@ -67,24 +81,19 @@ pub fn distribute_interest(ctx: Context<DistributeInterest>) -> Result<()> {
         ctx.accounts.destination_vault_spl_token_account.amount
     );
 
-    // Perform SPL token transfer from admin vault to all vaults
-    let cpi_accounts = TransferChecked {
-        from: ctx.accounts.admin_vault_spl_token_account.to_account_info(),
-        to: ctx
-            .accounts
-            .destination_vault_spl_token_account
-            .to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        authority: admin_vault.to_account_info(), // PDA as authority
-    };
-
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token::transfer_checked(cpi_ctx, interest_amount, ProgramInfo::TOKEN_DECIMALS)?;
-
-    // Update vault balances
-    destination_vault.update_balance_after_deposit(interest_amount);
-    admin_vault.update_balance_after_withdrawal(interest_amount);
+    pay_interest(
+        &ctx.accounts.program_info,
+        &mut ctx.accounts.destination_vault,
+        &mut ctx.accounts.destination_vault_spl_token_account,
+        InterestReserve {
+            admin_vault: &ctx.accounts.admin_vault,
+            admin_vault_spl_token_account: &ctx.accounts.admin_vault_spl_token_account,
+            mint_config: &mut ctx.accounts.mint_config,
+            mint_stats: &mut ctx.accounts.mint_stats,
+            mint: &ctx.accounts.mint,
+            token_program: &ctx.accounts.token_program,
+        },
+    )?;
 
     // Fetch updated accounts post transfer
     ctx.accounts.admin_vault_spl_token_account.reload()?;
@@ -102,3 +111,152 @@ pub fn distribute_interest(ctx: Context<DistributeInterest>) -> Result<()> {
 
     Ok(())
 }
+
+/// The per-mint accounts interest is paid out of.
+pub(crate) struct InterestReserve<'a, 'info> {
+    pub admin_vault: &'a Account<'info, Vault>,
+    pub admin_vault_spl_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub mint_config: &'a mut Account<'info, MintConfig>,
+    pub mint_stats: &'a mut Account<'info, MintStats>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Accrue the mint's interest index and pay the vault the interest its shares have earned
+/// since it was last touched, out of the admin vault. Returns the amount received after
+/// transfer fees.
+///
+/// While an interest round is in progress for the mint the vault is paid its pro-rata share
+/// of the reserve, outside of one a reserve that cannot cover the interest pays what it holds.
+/// Either way the vault keeps its claim on the rest, during a round it is recorded as the
+/// vault's shortfall. Deposits and withdrawals pay interest first, so they must never fail
+/// because the reserve is short.
+pub(crate) fn pay_interest<'info>(
+    program_info: &Account<'info, ProgramInfo>,
+    vault: &mut Account<'info, Vault>,
+    vault_spl_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    reserve: InterestReserve<'_, 'info>,
+) -> Result<u64> {
+    let current_time = Clock::get()?.unix_timestamp;
+    reserve.mint_config.accrue_interest(current_time)?;
+
+    // calculate the interest the vault's shares have earned, locked vaults earn a bonus on top
+    // for the part earned before they unlocked
+    let earned_interest = vault.pending_interest(reserve.mint_config)?;
+    let bonus = program_info.calculate_locked_interest_bonus(
+        vault.interest_earned_while_locked(earned_interest, current_time)?,
+    )?;
+    let interest_amount = earned_interest
+        .checked_add(bonus)
+        .ok_or(VaultError::MathOverflow)?;
+    msg!("Interest amount to be transferred: {}", interest_amount);
+
+    if interest_amount == 0 {
+        return Ok(0);
+    }
+
+    let reserve_balance = reserve.admin_vault_spl_token_account.amount;
+    let round_active = reserve
+        .mint_stats
+        .is_round_active(current_time, reserve.mint_config.accrual_period);
+    let payout = if round_active {
+        reserve
+            .mint_stats
+            .round_payout(interest_amount)?
+            .min(reserve_balance)
+    } else {
+        interest_amount.min(reserve_balance)
+    };
+    let unpaid_claim = interest_amount - payout;
+    if unpaid_claim > 0 {
+        msg!(
+            "Insufficient balance in admin's token account, {} stays owed",
+            unpaid_claim
+        );
+    }
+
+    // A shortfall the vault was already recorded with during this round is replaced, the
+    // claim behind it is part of `unpaid_claim` again
+    let shortfall = if round_active { unpaid_claim } else { 0 };
+    if round_active {
+        let previous_shortfall = if vault.last_interest_at >= reserve.mint_stats.round_started_at {
+            vault.interest_shortfall
+        } else {
+            0
+        };
+        reserve
+            .mint_stats
+            .replace_shortfall(previous_shortfall, shortfall)?;
+    }
+
+    let balance_before_transfer = vault_spl_token_account.amount;
+
+    if payout > 0 {
+        let admin_vault = reserve.admin_vault;
+        let seeds = &[
+            Vault::ADMIN_SEED,
+            admin_vault.spl_token_mint.as_ref(),
+            &[admin_vault.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Perform SPL token transfer from admin vault to the vault
+        let cpi_accounts = TransferChecked {
+            from: reserve.admin_vault_spl_token_account.to_account_info(),
+            to: vault_spl_token_account.to_account_info(),
+            mint: reserve.mint.to_account_info(),
+            authority: admin_vault.to_account_info(), // PDA as authority
+        };
+
+        let cpi_program = reserve.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, payout, reserve.mint.decimals)?;
+    }
+
+    // Transfer fees are withheld from the destination, so only credit what actually arrived
+    vault_spl_token_account.reload()?;
+    let received_amount = vault_spl_token_account
+        .amount
+        .checked_sub(balance_before_transfer)
+        .ok_or(VaultError::BalanceUnderflow)?;
+
+    // Update vault balances, the interest is already backed by the vault's shares.
+    // The admin vault is funded straight into its token account, which was checked above.
+    vault.update_balance_after_deposit(received_amount)?;
+    vault.record_interest_payout(received_amount, current_time)?;
+    if round_active {
+        msg!("Interest shortfall recorded: {}", shortfall);
+        vault.record_interest_shortfall(shortfall);
+    }
+
+    emit!(InterestDistributed {
+        vault: vault.key(),
+        owner: vault.owner,
+        mint: vault.spl_token_mint,
+        amount: received_amount,
+        shortfall,
+        balance: vault.total_balance(),
+        timestamp: current_time,
+    });
+
+    // The bonus is not backed by the vault's shares, issue shares for it so it is not
+    // counted against future interest. Then give up the shares backing what transfer fees
+    // withheld, so it is not owed again. The unpaid claim keeps its shares.
+    let minted_shares = if bonus > 0 {
+        vault.mint_shares(bonus, reserve.mint_config)?
+    } else {
+        0
+    };
+    let forfeited_amount = payout - received_amount;
+    let burned_shares = if forfeited_amount > 0 {
+        vault.burn_shares(forfeited_amount, reserve.mint_config)?
+    } else {
+        0
+    };
+    reserve
+        .mint_stats
+        .record_deposit(received_amount, minted_shares)?;
+    reserve.mint_stats.record_withdrawal(0, burned_shares)?;
+
+    Ok(received_amount)
+}
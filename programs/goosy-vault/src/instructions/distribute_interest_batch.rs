@@ -0,0 +1,106 @@
+use crate::errors::VaultError;
+use crate::instructions::{pay_interest, InterestReserve};
+use crate::state::{MintConfig, MintStats, ProgramInfo, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Vaults are passed as (vault, vault token account) pairs in the remaining accounts, both
+/// writable. All of them have to hold the batch's mint.
+#[derive(Accounts)]
+pub struct DistributeInterestBatch<'info> {
+    #[account(address = mint_config.reserve_vault @ VaultError::InvalidReserveVault)]
+    admin_vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=admin_vault.key(),
+        associated_token::token_program=token_program,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+    )]
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn distribute_interest_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeInterestBatch<'info>>,
+) -> Result<()> {
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(VaultError::InvalidBatchAccounts.into());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut paid_vaults = 0;
+
+    for pair in pairs {
+        let mut vault = Account::<Vault>::try_from(&pair[0])?;
+        let mut vault_spl_token_account = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+
+        // the pair has to be a user vault of this mint and the token account it custodies funds in
+        if !pair[0].is_writable
+            || !pair[1].is_writable
+            || vault.key() == ctx.accounts.admin_vault.key()
+            || !ctx
+                .accounts
+                .mint_config
+                .verify_spl_token_type(vault.spl_token_mint)
+            || vault.spl_token_account != vault_spl_token_account.key()
+            || vault_spl_token_account.owner != vault.key()
+        {
+            return Err(VaultError::InvalidBatchAccounts.into());
+        }
+
+        // vaults that are not due yet are skipped so one of them does not fail the whole batch
+        if !vault.is_interest_due(current_time, ctx.accounts.mint_config.accrual_period) {
+            msg!("Interest not due yet for vault {}, skipping", vault.key());
+            continue;
+        }
+
+        pay_interest(
+            &ctx.accounts.program_info,
+            &mut vault,
+            &mut vault_spl_token_account,
+            InterestReserve {
+                admin_vault: &ctx.accounts.admin_vault,
+                admin_vault_spl_token_account: &ctx.accounts.admin_vault_spl_token_account,
+                mint_config: &mut ctx.accounts.mint_config,
+                mint_stats: &mut ctx.accounts.mint_stats,
+                mint: &ctx.accounts.mint,
+                token_program: &ctx.accounts.token_program,
+            },
+        )?;
+
+        // Remaining accounts are not written back by Anchor, persist the vault ourselves
+        vault.exit(&crate::ID)?;
+
+        // The next payout has to see what the previous ones took out of the admin vault
+        ctx.accounts.admin_vault_spl_token_account.reload()?;
+        paid_vaults += 1;
+    }
+
+    msg!(
+        "Interest distributed to {} of {} vaults",
+        paid_vaults,
+        ctx.remaining_accounts.len() / 2
+    );
+
+    Ok(())
+}
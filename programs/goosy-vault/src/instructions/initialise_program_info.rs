@@ -1,3 +1,6 @@
+use crate::errors::VaultError;
+use crate::events::AdminChanged;
+use crate::program::GoosyVault;
 use crate::state::ProgramInfo;
 use anchor_lang::prelude::*;
 
@@ -15,15 +18,32 @@ pub struct Initialize<'info> {
     #[account(mut)]
     payer: Signer<'info>,
 
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    program: Program<'info, GoosyVault>,
+
+    // Only the upgrade authority can initialise, so nobody can front-run a new deployment
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key())
+            @ VaultError::NotUpgradeAuthority
+    )]
+    program_data: Account<'info, ProgramData>,
+
     system_program: Program<'info, System>,
 }
 
 pub fn initialise_program_info(ctx: Context<Initialize>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
     ctx.accounts.program_info.set_inner(ProgramInfo::new(
         ctx.bumps.program_info,
         ctx.accounts.payer.key(),
         0,
     ));
 
+    emit!(AdminChanged {
+        previous_admin: Pubkey::default(),
+        new_admin: ctx.accounts.program_info.admin,
+        timestamp: current_time,
+    });
+
     Ok(())
 }
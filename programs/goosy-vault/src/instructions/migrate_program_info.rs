@@ -0,0 +1,54 @@
+use crate::events::AccountMigrated;
+use crate::instructions::grow_account;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateProgramInfo<'info> {
+    /// CHECK: may still be in a layout Anchor cannot deserialize, the admin is checked once it
+    /// has been grown to the current size
+    #[account(mut, seeds = [ProgramInfo::SEED.as_bytes()], bump)]
+    program_info: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_program_info(ctx: Context<MigrateProgramInfo>) -> Result<()> {
+    let program_info_info = ctx.accounts.program_info.to_account_info();
+    grow_account(
+        &program_info_info,
+        &ProgramInfo::discriminator(),
+        8 + ProgramInfo::MAXIMUM_SPACE,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+
+    // The admin is the first field of every layout, so it reads the same after the realloc
+    let mut program_info =
+        ProgramInfo::try_deserialize(&mut &program_info_info.try_borrow_data()?[..])?;
+    if program_info.admin != ctx.accounts.admin.key() {
+        return Err(ErrorCode::ConstraintHasOne.into());
+    }
+
+    let from_version = program_info.migrate()?;
+    program_info.try_serialize(&mut &mut program_info_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Program info migrated from version {} to {}",
+        from_version,
+        ProgramInfo::CURRENT_VERSION
+    );
+
+    emit!(AccountMigrated {
+        account: program_info_info.key(),
+        from_version,
+        to_version: ProgramInfo::CURRENT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::errors::VaultError;
+use crate::events::AccountMigrated;
+use crate::state::{ProgramInfo, Vault};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+/// Signed by the admin, who pays for growing baseline vaults to the current layout. Baseline
+/// vaults kept their balance in the owner's token account, only the owner can move it into the
+/// vault's custody afterwards with `take_vault_custody`.
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: may still be in a layout Anchor cannot deserialize, the discriminator is checked
+    /// before it is grown to the current size
+    #[account(mut, owner = crate::ID)]
+    vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    grow_account(
+        &vault_info,
+        &Vault::discriminator(),
+        8 + Vault::MAXIMUM_SPACE,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Admin vaults are derived from different seeds and are not migrated
+    let mut vault = Vault::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+    if !vault.is_user_vault_address(&vault_info.key(), &crate::ID) {
+        return Err(ErrorCode::ConstraintSeeds.into());
+    }
+    if ctx.accounts.mint.key() != vault.spl_token_mint {
+        return Err(VaultError::InvalidSPLTokenType.into());
+    }
+
+    let from_version = vault.migrate(ctx.accounts.mint.decimals)?;
+    vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Vault {} migrated from version {} to {}",
+        vault_info.key(),
+        from_version,
+        Vault::CURRENT_VERSION
+    );
+
+    emit!(AccountMigrated {
+        account: vault_info.key(),
+        from_version,
+        to_version: Vault::CURRENT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Grow `account` to `space` bytes, topping up its rent from `payer`. New bytes are zeroed so
+/// fields appended since the account was written read as zero.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    discriminator: &[u8; 8],
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account.data_len() < 8 || account.try_borrow_data()?[..8] != discriminator[..] {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    account.realloc(space, true)?;
+    Ok(())
+}
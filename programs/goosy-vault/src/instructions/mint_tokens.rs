@@ -1,23 +1,43 @@
-use crate::state::Vault;
+use crate::errors::VaultError;
+use crate::events::TokensMinted;
+use crate::state::{MintConfig, ProgramInfo, Vault};
 use anchor_lang::prelude::*;
-use anchor_spl::token;
-use anchor_spl::token::{Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, MintTo, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct MintTokensTo<'info> {
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
-    pub destination_vault_spl_token_account: Account<'info, TokenAccount>,
-    #[account()]
+    pub destination_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(seeds = [Vault::ADMIN_SEED, mint.key().as_ref()], bump = admin_vault.bump)]
     admin_vault: Account<'info, Vault>,
-    pub token_program: Program<'info, Token>,
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
     #[account(mut)]
-    pub fee_payer: Signer<'info>,
+    pub admin: Signer<'info>,
 }
 
 pub fn mint_tokens_to(ctx: Context<MintTokensTo>, amount: u64) -> Result<()> {
-    let seeds = &[b"admin-vault".as_ref(), &[ctx.accounts.admin_vault.bump]];
+    // Count the amount against this epoch's mint cap of the mint
+    let epoch = Clock::get()?.epoch;
+    ctx.accounts.mint_config.record_mint(amount, epoch)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        Vault::ADMIN_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.admin_vault.bump],
+    ];
     let signer_seeds = &[&seeds[..]];
 
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -39,7 +59,7 @@ pub fn mint_tokens_to(ctx: Context<MintTokensTo>, amount: u64) -> Result<()> {
 
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-    token::mint_to(cpi_ctx, amount)?;
+    token_interface::mint_to(cpi_ctx, amount)?;
 
     // Fetch updated accounts post transfer
     ctx.accounts.destination_vault_spl_token_account.reload()?;
@@ -50,5 +70,14 @@ pub fn mint_tokens_to(ctx: Context<MintTokensTo>, amount: u64) -> Result<()> {
         ctx.accounts.destination_vault_spl_token_account.amount
     );
 
+    emit!(TokensMinted {
+        admin_vault: ctx.accounts.admin_vault.key(),
+        mint: ctx.accounts.mint.key(),
+        destination: ctx.accounts.destination_vault_spl_token_account.key(),
+        amount,
+        balance: ctx.accounts.destination_vault_spl_token_account.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
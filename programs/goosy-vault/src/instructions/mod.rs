@@ -1,15 +1,65 @@
+mod accept_admin;
+mod accrue_interest;
+mod close_vault;
+mod collect_fees;
 mod create_admin_vault;
+mod create_owner_vault;
 mod create_vault;
+mod create_vault_authority;
 mod deposit_spl_tokens;
 mod distribute_interest;
+mod distribute_interest_batch;
 mod initialise_program_info;
+mod migrate_program_info;
+mod migrate_vault;
 mod mint_tokens;
+mod propose_admin;
+mod register_mint;
+mod set_deposit_caps;
+mod set_fee_params;
+mod set_interest_params;
+mod set_lock_params;
+mod set_mint_cap;
+mod set_mint_enabled;
+mod set_paused;
+mod set_treasury;
+mod set_vault_delegate;
+mod start_interest_round;
+mod sweep_legacy_reserve;
+mod take_vault_custody;
+mod transfer_between_vaults;
+mod update_vault_authority;
 mod withdraw_spl_tokens;
 
+pub use accept_admin::*;
+pub use accrue_interest::*;
+pub use close_vault::*;
+pub use collect_fees::*;
 pub use create_admin_vault::*;
+pub use create_owner_vault::*;
 pub use create_vault::*;
+pub use create_vault_authority::*;
 pub use deposit_spl_tokens::*;
 pub use distribute_interest::*;
+pub use distribute_interest_batch::*;
 pub use initialise_program_info::*;
+pub use migrate_program_info::*;
+pub use migrate_vault::*;
 pub use mint_tokens::*;
+pub use propose_admin::*;
+pub use register_mint::*;
+pub use set_deposit_caps::*;
+pub use set_fee_params::*;
+pub use set_interest_params::*;
+pub use set_lock_params::*;
+pub use set_mint_cap::*;
+pub use set_mint_enabled::*;
+pub use set_paused::*;
+pub use set_treasury::*;
+pub use set_vault_delegate::*;
+pub use start_interest_round::*;
+pub use sweep_legacy_reserve::*;
+pub use take_vault_custody::*;
+pub use transfer_between_vaults::*;
+pub use update_vault_authority::*;
 pub use withdraw_spl_tokens::*;
@@ -0,0 +1,28 @@
+use crate::events::AdminProposed;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+}
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.program_info.propose_admin(new_admin);
+
+    emit!(AdminProposed {
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
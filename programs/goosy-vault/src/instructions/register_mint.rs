@@ -0,0 +1,99 @@
+use crate::events::MintRegistered;
+use crate::state::{MintConfig, MintStats, ProgramInfo, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// The mint's admin vault has to exist first, it becomes the reserve interest is paid from.
+#[derive(Accounts)]
+pub struct RegisterMint<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    // The seeds make it the mint's reserve, its recorded owner is whoever was admin when it
+    // was created and goes stale once the role is handed over
+    #[account(seeds = [Vault::ADMIN_SEED, mint.key().as_ref()], bump = admin_vault.bump)]
+    admin_vault: Account<'info, Vault>,
+
+    #[account(
+    init,
+    payer = admin,
+    seeds = [MintConfig::SEED, mint.key().as_ref()],
+    bump,
+    space = 8 + MintConfig::MAXIMUM_SPACE
+    )]
+    mint_config: Account<'info, MintConfig>,
+
+    // Totals of the mint's vaults, created along with the config they are accrued against
+    #[account(
+    init,
+    payer = admin,
+    seeds = [MintStats::SEED, mint.key().as_ref()],
+    bump,
+    space = 8 + MintStats::MAXIMUM_SPACE
+    )]
+    mint_stats: Account<'info, MintStats>,
+
+    // Protocol fees on the mint's deposits and withdrawals are held here until collected. Anyone
+    // can create it ahead of the mint stats, so an existing one is reused.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = mint_stats,
+        associated_token::token_program = token_program,
+    )]
+    fee_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+pub fn register_mint(
+    ctx: Context<RegisterMint>,
+    interest_rate_bps: u16,
+    accrual_period: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut mint_config = MintConfig::new(
+        ctx.accounts.mint.key(),
+        ctx.bumps.mint_config,
+        ctx.accounts.mint.decimals,
+        ctx.accounts.admin_vault.key(),
+        current_time,
+    );
+    mint_config.set_interest_params(interest_rate_bps, accrual_period)?;
+    ctx.accounts.mint_config.set_inner(mint_config);
+    ctx.accounts.mint_stats.set_inner(MintStats::new(
+        ctx.accounts.mint.key(),
+        ctx.bumps.mint_stats,
+    ));
+
+    msg!(
+        "Mint {} registered with {} bps interest every {} seconds",
+        ctx.accounts.mint.key(),
+        interest_rate_bps,
+        accrual_period
+    );
+
+    emit!(MintRegistered {
+        mint: ctx.accounts.mint.key(),
+        reserve_vault: ctx.accounts.admin_vault.key(),
+        decimals: ctx.accounts.mint.decimals,
+        interest_rate_bps,
+        accrual_period,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::events::DepositCapsUpdated;
+use crate::state::{MintConfig, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetDepositCaps<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn set_deposit_caps(
+    ctx: Context<SetDepositCaps>,
+    max_vault_balance: u64,
+    max_total_deposits: u64,
+    min_deposit: u64,
+) -> Result<()> {
+    ctx.accounts.mint_config.set_deposit_caps(
+        max_vault_balance,
+        max_total_deposits,
+        min_deposit,
+    )?;
+
+    msg!(
+        "Deposit caps of mint {} updated: {} per vault, {} in total, {} minimum deposit",
+        ctx.accounts.mint.key(),
+        max_vault_balance,
+        max_total_deposits,
+        min_deposit
+    );
+
+    emit!(DepositCapsUpdated {
+        mint: ctx.accounts.mint.key(),
+        max_vault_balance,
+        max_total_deposits,
+        min_deposit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
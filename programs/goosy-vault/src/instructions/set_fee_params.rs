@@ -0,0 +1,47 @@
+use crate::events::FeeParamsUpdated;
+use crate::state::{MintConfig, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetFeeParams<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn set_fee_params(
+    ctx: Context<SetFeeParams>,
+    deposit_fee_bps: u16,
+    withdrawal_fee_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .mint_config
+        .set_fee_params(deposit_fee_bps, withdrawal_fee_bps)?;
+
+    msg!(
+        "Fee params of mint {} updated: {} bps on deposits, {} bps on withdrawals",
+        ctx.accounts.mint.key(),
+        deposit_fee_bps,
+        withdrawal_fee_bps
+    );
+
+    emit!(FeeParamsUpdated {
+        mint: ctx.accounts.mint.key(),
+        deposit_fee_bps,
+        withdrawal_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::events::InterestParamsUpdated;
+use crate::state::{MintConfig, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetInterestParams<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn set_interest_params(
+    ctx: Context<SetInterestParams>,
+    interest_rate_bps: u16,
+    accrual_period: i64,
+) -> Result<()> {
+    let mint_config = &mut ctx.accounts.mint_config;
+
+    // Settle the interest earned at the old rate before switching to the new one
+    let current_time = Clock::get()?.unix_timestamp;
+    mint_config.accrue_interest(current_time)?;
+    mint_config.set_interest_params(interest_rate_bps, accrual_period)?;
+
+    msg!(
+        "Interest params of mint {} updated: {} bps every {} seconds",
+        mint_config.mint,
+        interest_rate_bps,
+        accrual_period
+    );
+
+    emit!(InterestParamsUpdated {
+        mint: mint_config.mint,
+        interest_rate_bps,
+        accrual_period,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
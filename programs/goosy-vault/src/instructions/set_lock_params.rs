@@ -0,0 +1,40 @@
+use crate::events::LockParamsUpdated;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetLockParams<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+}
+
+pub fn set_lock_params(
+    ctx: Context<SetLockParams>,
+    early_withdrawal_penalty_bps: u16,
+    locked_interest_bonus_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .program_info
+        .set_lock_params(early_withdrawal_penalty_bps, locked_interest_bonus_bps)?;
+
+    msg!(
+        "Lock params updated: {} bps early withdrawal penalty, {} bps locked interest bonus",
+        early_withdrawal_penalty_bps,
+        locked_interest_bonus_bps
+    );
+
+    emit!(LockParamsUpdated {
+        early_withdrawal_penalty_bps,
+        locked_interest_bonus_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
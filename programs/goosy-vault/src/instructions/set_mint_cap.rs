@@ -0,0 +1,39 @@
+use crate::events::MintCapUpdated;
+use crate::state::{MintConfig, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetMintCap<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn set_mint_cap(ctx: Context<SetMintCap>, mint_cap_per_epoch: u64) -> Result<()> {
+    ctx.accounts.mint_config.set_mint_cap(mint_cap_per_epoch);
+
+    msg!(
+        "Mint cap of {} updated: {} per epoch",
+        ctx.accounts.mint.key(),
+        mint_cap_per_epoch
+    );
+
+    emit!(MintCapUpdated {
+        mint: ctx.accounts.mint.key(),
+        mint_cap_per_epoch,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
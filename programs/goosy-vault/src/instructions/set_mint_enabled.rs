@@ -0,0 +1,48 @@
+use crate::events::MintEnabledUpdated;
+use crate::state::{MintConfig, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// Disabling only stops new vaults and deposits, existing vaults can still be withdrawn from
+#[derive(Accounts)]
+pub struct SetMintEnabled<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+}
+
+pub fn set_mint_enabled(
+    ctx: Context<SetMintEnabled>,
+    vaults_enabled: bool,
+    deposits_enabled: bool,
+) -> Result<()> {
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.vaults_enabled = vaults_enabled;
+    mint_config.deposits_enabled = deposits_enabled;
+
+    msg!(
+        "Mint {} updated: vaults enabled {}, deposits enabled {}",
+        ctx.accounts.mint.key(),
+        vaults_enabled,
+        deposits_enabled
+    );
+
+    emit!(MintEnabledUpdated {
+        mint: ctx.accounts.mint.key(),
+        vaults_enabled,
+        deposits_enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::events::PauseUpdated;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+}
+
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool, withdrawals_paused: bool) -> Result<()> {
+    ctx.accounts
+        .program_info
+        .set_paused(paused, withdrawals_paused);
+
+    msg!(
+        "Program paused: {}, withdrawals paused: {}",
+        paused,
+        withdrawals_paused
+    );
+
+    emit!(PauseUpdated {
+        paused,
+        withdrawals_paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
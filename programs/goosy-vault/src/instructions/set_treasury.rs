@@ -0,0 +1,47 @@
+use crate::errors::VaultError;
+use crate::events::TreasuryUpdated;
+use crate::state::{MintStats, ProgramInfo};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        constraint = treasury_spl_token_account.mint == mint.key()
+            @ VaultError::InvalidTokenAccountMint,
+    )]
+    treasury_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+    let treasury = ctx.accounts.treasury_spl_token_account.key();
+    ctx.accounts.mint_stats.treasury = treasury;
+
+    msg!(
+        "Treasury of mint {} updated: {}",
+        ctx.accounts.mint.key(),
+        treasury
+    );
+
+    emit!(TreasuryUpdated {
+        mint: ctx.accounts.mint.key(),
+        treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::events::DelegateUpdated;
+use crate::state::{Vault, VaultAuthority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetVaultDelegate<'info> {
+    #[account(has_one = owner)]
+    vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VaultAuthority::SEED, vault.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    vault_authority: Account<'info, VaultAuthority>,
+
+    owner: Signer<'info>,
+}
+
+pub fn set_vault_delegate(
+    ctx: Context<SetVaultDelegate>,
+    delegate: Pubkey,
+    daily_withdrawal_limit: Option<u64>,
+) -> Result<()> {
+    ctx.accounts
+        .vault_authority
+        .set_delegate(delegate, daily_withdrawal_limit)?;
+
+    match daily_withdrawal_limit {
+        Some(limit) => msg!("Delegate {} can withdraw {} per day", delegate, limit),
+        None => msg!("Delegate {} removed", delegate),
+    }
+
+    emit!(DelegateUpdated {
+        vault: ctx.accounts.vault.key(),
+        delegate,
+        daily_withdrawal_limit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::errors::VaultError;
+use crate::events::InterestRoundStarted;
+use crate::state::{MintConfig, MintStats, ProgramInfo, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct StartInterestRound<'info> {
+    #[account(address = mint_config.reserve_vault @ VaultError::InvalidReserveVault)]
+    admin_vault: Account<'info, Vault>,
+
+    #[account(
+        associated_token::mint=mint,
+        associated_token::authority=admin_vault.key(),
+        associated_token::token_program=token_program,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+    )]
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// The admin starts a round once the previous one has run for an accrual period, after
+/// funding the reserve for it. The payout ratio is fixed for the whole round, so it is not
+/// left to whoever happens to call first.
+pub fn start_interest_round(ctx: Context<StartInterestRound>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.mint_config.accrue_interest(current_time)?;
+
+    let reserve = ctx.accounts.admin_vault_spl_token_account.amount;
+    let mint_stats = &mut ctx.accounts.mint_stats;
+    mint_stats.start_round(reserve, &ctx.accounts.mint_config, current_time)?;
+
+    msg!(
+        "Interest round started: reserve {}, owed {}, payout ratio {}",
+        mint_stats.round_reserve,
+        mint_stats.round_interest_owed,
+        mint_stats.round_payout_ratio
+    );
+
+    emit!(InterestRoundStarted {
+        mint: mint_stats.mint,
+        reserve: mint_stats.round_reserve,
+        interest_owed: mint_stats.round_interest_owed,
+        payout_ratio: mint_stats.round_payout_ratio,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
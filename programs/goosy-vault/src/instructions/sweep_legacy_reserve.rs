@@ -0,0 +1,82 @@
+use crate::errors::VaultError;
+use crate::events::LegacyReserveSwept;
+use crate::state::{ProgramInfo, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Before reserves were keyed by mint there was a single admin vault at `[ADMIN_SEED]`. Its
+/// token accounts can no longer be paid out of, the admin sweeps them into the mint's reserve.
+#[derive(Accounts)]
+pub struct SweepLegacyReserve<'info> {
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        has_one = admin,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+
+    admin: Signer<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: only signs for its token account, it may still be in the baseline vault layout
+    #[account(seeds = [Vault::ADMIN_SEED], bump)]
+    legacy_admin_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = legacy_admin_vault,
+        associated_token::token_program = token_program,
+    )]
+    legacy_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [Vault::ADMIN_SEED, mint.key().as_ref()], bump = admin_vault.bump)]
+    admin_vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+    )]
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn sweep_legacy_reserve(ctx: Context<SweepLegacyReserve>) -> Result<()> {
+    let amount = ctx.accounts.legacy_spl_token_account.amount;
+    if amount == 0 {
+        return Err(VaultError::LegacyReserveEmpty.into());
+    }
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.legacy_spl_token_account.to_account_info(),
+        to: ctx.accounts.admin_vault_spl_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.legacy_admin_vault.to_account_info(), // PDA as authority
+    };
+    let bump = [ctx.bumps.legacy_admin_vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[Vault::ADMIN_SEED, &bump]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    msg!(
+        "Legacy reserve swept into the mint's admin vault: {}",
+        amount
+    );
+
+    emit!(LegacyReserveSwept {
+        mint: ctx.accounts.mint.key(),
+        admin_vault: ctx.accounts.admin_vault.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
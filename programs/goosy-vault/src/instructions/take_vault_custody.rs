@@ -0,0 +1,97 @@
+use crate::errors::VaultError;
+use crate::events::VaultCustodyTaken;
+use crate::state::{MintConfig, MintStats, Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Signed by the owner of a vault `migrate_vault` has brought to the current layout. The balance
+/// baseline vaults kept in the owner's token account is moved into a token account owned by the
+/// vault PDA, like every newer vault's, and starts earning interest.
+#[derive(Accounts)]
+pub struct TakeVaultCustody<'info> {
+    #[account(mut, has_one = owner @ VaultError::InvalidVaultOwner)]
+    vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = vault.spl_token_account @ VaultError::InvalidVaultTokenAccount,
+    )]
+    legacy_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    spl_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = vault.spl_token_mint @ VaultError::InvalidSPLTokenType)]
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [MintConfig::SEED, mint.key().as_ref()], bump = mint_config.bump)]
+    mint_config: Account<'info, MintConfig>,
+
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+pub fn take_vault_custody(ctx: Context<TakeVaultCustody>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    if vault.spl_token_account == ctx.accounts.spl_token_account.key() {
+        return Err(VaultError::AlreadyInCustody.into());
+    }
+    if !vault.is_user_vault_address(&vault.key(), &crate::ID) {
+        return Err(ErrorCode::ConstraintSeeds.into());
+    }
+
+    // Move the recorded balance into the vault's custody, crediting what actually arrived
+    let amount = vault
+        .total_balance()
+        .min(ctx.accounts.legacy_spl_token_account.amount);
+    let balance_before_transfer = ctx.accounts.spl_token_account.amount;
+    if amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.legacy_spl_token_account.to_account_info(),
+            to: ctx.accounts.spl_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    }
+    ctx.accounts.spl_token_account.reload()?;
+    let balance = ctx
+        .accounts
+        .spl_token_account
+        .amount
+        .checked_sub(balance_before_transfer)
+        .ok_or(VaultError::BalanceUnderflow)?;
+    vault.take_custody(ctx.accounts.spl_token_account.key(), balance);
+
+    // Issue shares for the balance so it earns interest from now on
+    let current_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.mint_config.accrue_interest(current_time)?;
+    let shares = vault.mint_shares(balance, &ctx.accounts.mint_config)?;
+    ctx.accounts.mint_stats.record_deposit(balance, shares)?;
+
+    emit!(VaultCustodyTaken {
+        vault: vault.key(),
+        spl_token_account: vault.spl_token_account,
+        balance,
+        shares,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::events::TransferredBetweenVaults;
+use crate::instructions::transfer_from_vault;
+use crate::state::{signer_keys, MintConfig, MintStats, ProgramInfo, VaultAuthority};
+use crate::{errors::VaultError, state::Vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Moves tokens from one of the owner's vaults into any user vault of the same mint, without
+/// going through a wallet. No protocol fee is taken, and the amount stays counted in the mint's
+/// total deposits. Co-signers for large transfers are passed as remaining accounts.
+#[derive(Accounts)]
+pub struct TransferBetweenVaults<'info> {
+    #[account(
+        mut,
+        has_one = owner @ VaultError::InvalidVaultOwner,
+        constraint = mint_config.verify_spl_token_type(source_vault.spl_token_mint)
+            @ VaultError::InvalidSPLTokenType,
+    )]
+    source_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        constraint = source_vault_spl_token_account.key() == source_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = source_vault_spl_token_account.owner == source_vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    source_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    // Admin vaults are derived from different seeds, interest reserves cannot be paid into
+    #[account(
+        mut,
+        constraint = destination_vault.key() != source_vault.key() @ VaultError::SameVault,
+        constraint = destination_vault.is_user_vault_address(&destination_vault.key(), &crate::ID),
+        constraint = mint_config.verify_spl_token_type(destination_vault.spl_token_mint)
+            @ VaultError::InvalidSPLTokenType,
+    )]
+    destination_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        constraint = destination_vault_spl_token_account.key() == destination_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = destination_vault_spl_token_account.owner == destination_vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    destination_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [VaultAuthority::SEED, source_vault.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    vault_authority: Option<Account<'info, VaultAuthority>>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.deposits_enabled @ VaultError::DepositsDisabled,
+    )]
+    mint_config: Account<'info, MintConfig>,
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = !program_info.paused @ VaultError::ProgramPaused,
+        constraint = !program_info.withdrawals_paused @ VaultError::WithdrawalsPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn transfer_between_vaults(ctx: Context<TransferBetweenVaults>, amount: u64) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let owner = ctx.accounts.owner.key();
+
+    // The transfer leaves the source vault like a withdrawal, so its co-signers still apply
+    match ctx.accounts.vault_authority.as_mut() {
+        Some(vault_authority) => {
+            let signers = signer_keys(&owner, ctx.remaining_accounts);
+            vault_authority.authorize_withdrawal(&owner, &owner, &signers, amount, current_time)?;
+        }
+        None if ctx.accounts.source_vault.has_vault_authority => {
+            return Err(VaultError::MissingVaultAuthority.into());
+        }
+        None => {}
+    }
+
+    // Moving funds out of a term deposit would dodge the early withdrawal penalty
+    if ctx.accounts.source_vault.is_locked(current_time) {
+        return Err(VaultError::VaultLocked.into());
+    }
+
+    if !ctx.accounts.source_vault.has_sufficient_balance(amount)
+        || ctx.accounts.source_vault_spl_token_account.amount < amount
+    {
+        return Err(VaultError::InsufficientBalance.into());
+    }
+
+    // The amount is already counted in the mint's total deposits, only the vault caps apply
+    ctx.accounts.mint_config.verify_deposit_caps(
+        ctx.accounts
+            .mint_stats
+            .total_deposits
+            .saturating_sub(amount),
+        ctx.accounts.destination_vault.total_balance(),
+        amount,
+    )?;
+
+    // The destination may receive less than the amount for Token-2022 mints with transfer fees
+    let balance_before_transfer = ctx.accounts.destination_vault_spl_token_account.amount;
+    transfer_from_vault(
+        &ctx.accounts.source_vault,
+        &ctx.accounts.source_vault_spl_token_account,
+        ctx.accounts
+            .destination_vault_spl_token_account
+            .to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    ctx.accounts.destination_vault_spl_token_account.reload()?;
+    let received_amount = ctx
+        .accounts
+        .destination_vault_spl_token_account
+        .amount
+        .checked_sub(balance_before_transfer)
+        .ok_or(VaultError::BalanceUnderflow)?;
+
+    // Shares move at the current index, interest either vault has earned so far stays with it
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.accrue_interest(current_time)?;
+
+    let source_vault = &mut ctx.accounts.source_vault;
+    source_vault.update_balance_after_withdrawal(amount)?;
+    let burned_shares = source_vault.burn_shares(amount, mint_config)?;
+
+    let destination_vault = &mut ctx.accounts.destination_vault;
+    destination_vault.update_balance_after_deposit(received_amount)?;
+    let minted_shares = destination_vault.mint_shares(received_amount, mint_config)?;
+
+    let mint_stats = &mut ctx.accounts.mint_stats;
+    mint_stats.record_withdrawal(amount, burned_shares)?;
+    mint_stats.record_deposit(received_amount, minted_shares)?;
+
+    msg!(
+        "Transferred {} from vault {} to vault {}",
+        amount,
+        source_vault.key(),
+        destination_vault.key()
+    );
+
+    emit!(TransferredBetweenVaults {
+        source_vault: source_vault.key(),
+        destination_vault: destination_vault.key(),
+        owner,
+        mint: source_vault.spl_token_mint,
+        amount,
+        received_amount,
+        source_balance: source_vault.total_balance(),
+        destination_balance: destination_vault.total_balance(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
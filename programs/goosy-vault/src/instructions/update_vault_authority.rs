@@ -0,0 +1,48 @@
+use crate::events::VaultAuthorityUpdated;
+use crate::state::{signer_keys, Vault, VaultAuthority};
+use anchor_lang::prelude::*;
+
+/// Co-signers sign the transaction and are passed as remaining accounts.
+#[derive(Accounts)]
+pub struct UpdateVaultAuthority<'info> {
+    #[account(has_one = owner)]
+    vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VaultAuthority::SEED, vault.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    vault_authority: Account<'info, VaultAuthority>,
+
+    owner: Signer<'info>,
+}
+
+pub fn update_vault_authority(
+    ctx: Context<UpdateVaultAuthority>,
+    co_signers: Vec<Pubkey>,
+    threshold: u8,
+    multisig_threshold_amount: u64,
+) -> Result<()> {
+    // Changing the co-signers takes as many signatures as a large withdrawal
+    let vault_owner = ctx.accounts.vault.owner;
+    let signers = signer_keys(&ctx.accounts.owner.key(), ctx.remaining_accounts);
+    let vault_authority = &mut ctx.accounts.vault_authority;
+    vault_authority.verify_threshold(&vault_owner, &signers)?;
+    vault_authority.set_multisig(
+        &vault_owner,
+        co_signers,
+        threshold,
+        multisig_threshold_amount,
+    )?;
+
+    emit!(VaultAuthorityUpdated {
+        vault: ctx.accounts.vault.key(),
+        co_signers: vault_authority.co_signers.clone(),
+        threshold,
+        multisig_threshold_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,66 +1,254 @@
+use crate::events::Withdrawn;
+use crate::instructions::{pay_interest, InterestReserve};
+use crate::state::{signer_keys, MintConfig, MintStats, ProgramInfo, VaultAuthority};
 use crate::{errors::VaultError, state::Vault};
 use anchor_lang::context::{Context, CpiContext};
 use anchor_lang::prelude::*;
 use anchor_lang::{Accounts, ToAccountInfo};
-use anchor_spl::token;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token_interface;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct WithdrawSPLTokens<'info> {
     #[account(mut)]
     vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        constraint = vault_spl_token_account.key() == vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+        constraint = vault_spl_token_account.mint == vault.spl_token_mint
+            @ VaultError::InvalidTokenAccountMint,
+        constraint = vault_spl_token_account.owner == vault.key()
+            @ VaultError::InvalidTokenAccountAuthority,
+    )]
+    vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
-    vault_spl_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    user_spl_token_account: Account<'info, TokenAccount>,
+    user_spl_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(signer)]
     payer: Signer<'info>,
-    #[account(signer, constraint = owner.key() == vault.owner)]
-    owner: Signer<'info>,
-    token_program: Program<'info, Token>,
+    // The owner, or one of the vault's delegates
+    #[account(signer)]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [VaultAuthority::SEED, vault.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    vault_authority: Option<Account<'info, VaultAuthority>>,
+    #[account()]
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.verify_spl_token_type(vault.spl_token_mint)
+            @ VaultError::InvalidSPLTokenType,
+    )]
+    mint_config: Account<'info, MintConfig>,
+    #[account(mut, seeds = [MintStats::SEED, mint.key().as_ref()], bump = mint_stats.bump)]
+    mint_stats: Account<'info, MintStats>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=mint_stats,
+        associated_token::token_program=token_program,
+    )]
+    fee_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [ProgramInfo::SEED.as_bytes()],
+        bump = program_info.bump,
+        constraint = !program_info.withdrawals_paused @ VaultError::WithdrawalsPaused,
+    )]
+    program_info: Account<'info, ProgramInfo>,
+    #[account(
+        mut,
+        address = mint_config.reserve_vault @ VaultError::InvalidReserveVault,
+        constraint = admin_vault.key() != vault.key(),
+    )]
+    admin_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=admin_vault.key(),
+        associated_token::token_program=token_program,
+        constraint = admin_vault_spl_token_account.key() == admin_vault.spl_token_account
+            @ VaultError::InvalidVaultTokenAccount,
+    )]
+    admin_vault_spl_token_account: InterfaceAccount<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn withdraw_spl_tokens(ctx: Context<WithdrawSPLTokens>, amount: u64) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-
-    // verify the owner of the vault
-    if ctx.accounts.owner.key() != vault.owner {
-        return Err(VaultError::InvalidVaultOwner.into());
+    // verify the signer may withdraw from the vault. Vaults with a vault authority have to
+    // present it, co-signers for large withdrawals are passed as remaining accounts.
+    let current_time = Clock::get()?.unix_timestamp;
+    let vault_owner = ctx.accounts.vault.owner;
+    let authority = ctx.accounts.authority.key();
+    match ctx.accounts.vault_authority.as_mut() {
+        Some(vault_authority) => {
+            let signers = signer_keys(&authority, ctx.remaining_accounts);
+            vault_authority.authorize_withdrawal(
+                &vault_owner,
+                &authority,
+                &signers,
+                amount,
+                current_time,
+            )?;
+        }
+        None if ctx.accounts.vault.has_vault_authority => {
+            return Err(VaultError::MissingVaultAuthority.into());
+        }
+        None if authority != vault_owner => {
+            return Err(VaultError::InvalidVaultOwner.into());
+        }
+        None => {}
     }
 
     // verify if the vault mint matches the mint of the token account
-    if !vault.verify_spl_token_type(ctx.accounts.user_spl_token_account.mint) {
+    if !ctx
+        .accounts
+        .mint_config
+        .verify_spl_token_type(ctx.accounts.user_spl_token_account.mint)
+    {
         return Err(VaultError::InvalidSPLTokenType.into());
     }
 
-    // check if sufficient balance in user's token account
-    if ctx.accounts.user_spl_token_account.amount < amount {
+    // Pay out the interest earned so far so it can be withdrawn as well. While the program
+    // is paused no interest leaves the admin vault, the vault keeps its claim for later.
+    if !ctx.accounts.program_info.paused {
+        pay_interest(
+            &ctx.accounts.program_info,
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.vault_spl_token_account,
+            InterestReserve {
+                admin_vault: &ctx.accounts.admin_vault,
+                admin_vault_spl_token_account: &ctx.accounts.admin_vault_spl_token_account,
+                mint_config: &mut ctx.accounts.mint_config,
+                mint_stats: &mut ctx.accounts.mint_stats,
+                mint: &ctx.accounts.mint,
+                token_program: &ctx.accounts.token_program,
+            },
+        )?;
+    }
+
+    // check if sufficient balance in the vault and its token account
+    if !ctx.accounts.vault.has_sufficient_balance(amount)
+        || ctx.accounts.vault_spl_token_account.amount < amount
+    {
         return Err(VaultError::InsufficientBalance.into());
     }
 
+    // Locked vaults either refuse early withdrawals or pay a penalty to the admin vault
+    let penalty = if ctx.accounts.vault.is_locked(current_time) {
+        let program_info = &ctx.accounts.program_info;
+        if program_info.early_withdrawal_penalty_bps == 0 {
+            return Err(VaultError::VaultLocked.into());
+        }
+        program_info.calculate_early_withdrawal_penalty(amount)?
+    } else {
+        0
+    };
+
+    // The protocol fee is taken from what is left after the penalty, so the two together never
+    // exceed the amount withdrawn
+    let amount_after_penalty = amount
+        .checked_sub(penalty)
+        .ok_or(VaultError::BalanceUnderflow)?;
+    let fee = ctx
+        .accounts
+        .mint_config
+        .calculate_withdrawal_fee(amount_after_penalty)?;
+    let net_amount = amount_after_penalty
+        .checked_sub(fee)
+        .ok_or(VaultError::BalanceUnderflow)?;
+
     // Perform SPL token transfer from vault to user
-    let transfer_instruction = Transfer {
-        from: ctx
-            .accounts
-            .vault_spl_token_account
-            .to_account_info()
-            .clone(),
-        to: ctx
-            .accounts
-            .user_spl_token_account
-            .to_account_info()
-            .clone(),
-        authority: ctx.accounts.owner.to_account_info().clone(),
+    transfer_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_spl_token_account,
+        ctx.accounts.user_spl_token_account.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        net_amount,
+    )?;
+
+    if penalty > 0 {
+        msg!("Early withdrawal penalty paid to admin vault: {}", penalty);
+        transfer_from_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.vault_spl_token_account,
+            ctx.accounts.admin_vault_spl_token_account.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            penalty,
+        )?;
+    }
+
+    // The fee account may receive less than the fee for Token-2022 mints with transfer fees
+    let fee_received = if fee > 0 {
+        msg!("Protocol fee taken from withdrawal: {}", fee);
+        let balance_before_transfer = ctx.accounts.fee_spl_token_account.amount;
+        transfer_from_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.vault_spl_token_account,
+            ctx.accounts.fee_spl_token_account.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            fee,
+        )?;
+        ctx.accounts.fee_spl_token_account.reload()?;
+        ctx.accounts
+            .fee_spl_token_account
+            .amount
+            .checked_sub(balance_before_transfer)
+            .ok_or(VaultError::BalanceUnderflow)?
+    } else {
+        0
     };
 
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_instruction,
-    );
-    token::transfer(cpi_ctx, amount)?;
+    // Update vault balance and redeem the shares backing the withdrawn amount
+    let vault = &mut ctx.accounts.vault;
+    vault.update_balance_after_withdrawal(amount)?;
+    vault.record_fee(fee)?;
+    let shares = vault.burn_shares(amount, &ctx.accounts.mint_config)?;
+    ctx.accounts.mint_stats.record_withdrawal(amount, shares)?;
+    ctx.accounts.mint_stats.record_fee(fee_received)?;
 
-    // Update vault balance
-    vault.update_balance_after_withdrawal(amount);
+    emit!(Withdrawn {
+        vault: vault.key(),
+        owner: vault.owner,
+        authority,
+        mint: vault.spl_token_mint,
+        amount,
+        penalty,
+        fee,
+        net_amount,
+        balance: vault.total_balance(),
+        timestamp: current_time,
+    });
 
     Ok(())
 }
+
+/// Transfer `amount` out of the vault's token account, signed by the vault PDA.
+pub(crate) fn transfer_from_vault<'info>(
+    vault: &Account<'info, Vault>,
+    vault_spl_token_account: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: vault_spl_token_account.to_account_info(),
+        to,
+        mint: mint.to_account_info(),
+        authority: vault.to_account_info(), // PDA as authority
+    };
+
+    let cpi_program = token_program.to_account_info();
+    vault.with_signer_seeds(|signer_seeds| {
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+    })
+}
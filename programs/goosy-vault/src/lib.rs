@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use instructions::*;
 
 mod errors;
+pub mod events;
 mod instructions;
 pub mod state;
 
@@ -15,16 +16,74 @@ pub(crate) mod goosy_vault {
         instructions::initialise_program_info(ctx)
     }
 
-    pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
-        instructions::create_vault(ctx)
+    pub fn create_vault(ctx: Context<CreateVault>, lock_duration: Option<i64>) -> Result<()> {
+        instructions::create_vault(ctx, lock_duration)
+    }
+
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        instructions::close_vault(ctx)
+    }
+
+    pub fn create_owner_vault(
+        ctx: Context<CreateOwnerVault>,
+        nonce: u32,
+        lock_duration: Option<i64>,
+    ) -> Result<()> {
+        instructions::create_owner_vault(ctx, nonce, lock_duration)
     }
 
     pub fn create_admin_vault(ctx: Context<CreateAdminVault>) -> Result<()> {
         instructions::create_admin_vault(ctx)
     }
 
-    pub fn deposit_spl_tokens(ctx: Context<DepositSPLTokens>, deposit_amount: u64) -> Result<()> {
-        instructions::deposit_spl_tokens(ctx, deposit_amount)
+    pub fn register_mint(
+        ctx: Context<RegisterMint>,
+        interest_rate_bps: u16,
+        accrual_period: i64,
+    ) -> Result<()> {
+        instructions::register_mint(ctx, interest_rate_bps, accrual_period)
+    }
+
+    pub fn set_mint_enabled(
+        ctx: Context<SetMintEnabled>,
+        vaults_enabled: bool,
+        deposits_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_mint_enabled(ctx, vaults_enabled, deposits_enabled)
+    }
+
+    pub fn create_vault_authority(
+        ctx: Context<CreateVaultAuthority>,
+        co_signers: Vec<Pubkey>,
+        threshold: u8,
+        multisig_threshold_amount: u64,
+    ) -> Result<()> {
+        instructions::create_vault_authority(ctx, co_signers, threshold, multisig_threshold_amount)
+    }
+
+    pub fn update_vault_authority(
+        ctx: Context<UpdateVaultAuthority>,
+        co_signers: Vec<Pubkey>,
+        threshold: u8,
+        multisig_threshold_amount: u64,
+    ) -> Result<()> {
+        instructions::update_vault_authority(ctx, co_signers, threshold, multisig_threshold_amount)
+    }
+
+    pub fn set_vault_delegate(
+        ctx: Context<SetVaultDelegate>,
+        delegate: Pubkey,
+        daily_withdrawal_limit: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_vault_delegate(ctx, delegate, daily_withdrawal_limit)
+    }
+
+    pub fn deposit_spl_tokens(
+        ctx: Context<DepositSPLTokens>,
+        deposit_amount: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::deposit_spl_tokens(ctx, deposit_amount, memo)
     }
 
     pub fn withdraw_spl_tokens(
@@ -34,11 +93,109 @@ pub(crate) mod goosy_vault {
         instructions::withdraw_spl_tokens(ctx, withdrawal_amount)
     }
 
+    pub fn transfer_between_vaults(
+        ctx: Context<TransferBetweenVaults>,
+        transfer_amount: u64,
+    ) -> Result<()> {
+        instructions::transfer_between_vaults(ctx, transfer_amount)
+    }
+
     pub fn distribute_interest(ctx: Context<DistributeInterest>) -> Result<()> {
         instructions::distribute_interest(ctx)
     }
 
+    pub fn distribute_interest_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeInterestBatch<'info>>,
+    ) -> Result<()> {
+        instructions::distribute_interest_batch(ctx)
+    }
+
+    pub fn start_interest_round(ctx: Context<StartInterestRound>) -> Result<()> {
+        instructions::start_interest_round(ctx)
+    }
+
+    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        instructions::accrue_interest(ctx)
+    }
+
     pub fn mint_tokens_to(ctx: Context<MintTokensTo>, amount: u64) -> Result<()> {
         instructions::mint_tokens_to(ctx, amount)
     }
+
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+        withdrawals_paused: bool,
+    ) -> Result<()> {
+        instructions::set_paused(ctx, paused, withdrawals_paused)
+    }
+
+    pub fn set_mint_cap(ctx: Context<SetMintCap>, mint_cap_per_epoch: u64) -> Result<()> {
+        instructions::set_mint_cap(ctx, mint_cap_per_epoch)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    pub fn set_interest_params(
+        ctx: Context<SetInterestParams>,
+        interest_rate_bps: u16,
+        accrual_period: i64,
+    ) -> Result<()> {
+        instructions::set_interest_params(ctx, interest_rate_bps, accrual_period)
+    }
+
+    pub fn set_lock_params(
+        ctx: Context<SetLockParams>,
+        early_withdrawal_penalty_bps: u16,
+        locked_interest_bonus_bps: u16,
+    ) -> Result<()> {
+        instructions::set_lock_params(ctx, early_withdrawal_penalty_bps, locked_interest_bonus_bps)
+    }
+
+    pub fn set_fee_params(
+        ctx: Context<SetFeeParams>,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_fee_params(ctx, deposit_fee_bps, withdrawal_fee_bps)
+    }
+
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        instructions::set_treasury(ctx)
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        instructions::collect_fees(ctx)
+    }
+
+    pub fn set_deposit_caps(
+        ctx: Context<SetDepositCaps>,
+        max_vault_balance: u64,
+        max_total_deposits: u64,
+        min_deposit: u64,
+    ) -> Result<()> {
+        instructions::set_deposit_caps(ctx, max_vault_balance, max_total_deposits, min_deposit)
+    }
+
+    pub fn sweep_legacy_reserve(ctx: Context<SweepLegacyReserve>) -> Result<()> {
+        instructions::sweep_legacy_reserve(ctx)
+    }
+
+    pub fn migrate_program_info(ctx: Context<MigrateProgramInfo>) -> Result<()> {
+        instructions::migrate_program_info(ctx)
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault(ctx)
+    }
+
+    pub fn take_vault_custody(ctx: Context<TakeVaultCustody>) -> Result<()> {
+        instructions::take_vault_custody(ctx)
+    }
 }
@@ -0,0 +1,286 @@
+use crate::errors::VaultError;
+use crate::state::ProgramInfo;
+use anchor_lang::prelude::*;
+
+/// Configuration of one mint's market, registered by the admin before vaults can hold it.
+/// Interest accrues on a per-mint index, at the mint's own rate and period.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintConfig {
+    pub mint: Pubkey,            // 32
+    pub bump: u8,                // 1
+    pub decimals: u8,            // 1, decimals of the mint
+    pub reserve_vault: Pubkey,   // 32, admin vault interest and penalties are paid from and to
+    pub interest_rate_bps: u16,  // 2, interest paid per accrual period in basis points
+    pub accrual_period: i64,     // 8, length of an accrual period in seconds
+    pub interest_index: u128,    // 16, exchange rate between the mint's vault shares and tokens
+    pub index_updated_at: i64,   // 8, time the interest index was last accrued
+    pub deposit_fee_bps: u16,    // 2, protocol fee taken from deposits
+    pub withdrawal_fee_bps: u16, // 2, protocol fee taken from withdrawals
+    pub max_vault_balance: u64,  // 8, largest balance a vault can reach through deposits
+    pub max_total_deposits: u64, // 8, largest total deposits can bring the mint to
+    pub min_deposit: u64,        // 8, smallest amount a single deposit can be
+    pub vaults_enabled: bool,    // 1, new vaults can be created for the mint
+    pub deposits_enabled: bool,  // 1, the mint's vaults accept deposits
+    pub mint_cap_per_epoch: u64, // 8, maximum amount the admin vault can mint per epoch
+    pub minted_in_epoch: u64,    // 8, amount minted during `mint_epoch`
+    pub mint_epoch: u64,         // 8, epoch `minted_in_epoch` belongs to
+    pub version: u8,             // 1, layout version, see CURRENT_VERSION
+    reserved: [u8; 32],          // 32, room for new fields without a realloc
+}
+
+impl MintConfig {
+    pub const MAXIMUM_SPACE: usize =
+        32 + 1 + 1 + 32 + 2 + 8 + 16 + 8 + 2 + 2 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 8 + 1 + 32;
+    pub const SEED: &'static [u8] = b"mint-config";
+
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Fixed point scale of the interest index, one share is worth one token at this value.
+    pub const INTEREST_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+    /// Deposits are uncapped until the admin sets caps.
+    pub const DEFAULT_DEPOSIT_CAP: u64 = u64::MAX;
+
+    /// Minting is uncapped until the admin sets a cap.
+    pub const DEFAULT_MINT_CAP_PER_EPOCH: u64 = u64::MAX;
+
+    pub fn new(
+        mint: Pubkey,
+        bump: u8,
+        decimals: u8,
+        reserve_vault: Pubkey,
+        current_time: i64,
+    ) -> Self {
+        Self {
+            mint,
+            bump,
+            decimals,
+            reserve_vault,
+            interest_rate_bps: ProgramInfo::DEFAULT_INTEREST_RATE_BPS,
+            accrual_period: ProgramInfo::DEFAULT_ACCRUAL_PERIOD,
+            interest_index: Self::INTEREST_INDEX_SCALE,
+            index_updated_at: current_time,
+            max_vault_balance: Self::DEFAULT_DEPOSIT_CAP,
+            max_total_deposits: Self::DEFAULT_DEPOSIT_CAP,
+            vaults_enabled: true,
+            deposits_enabled: true,
+            mint_cap_per_epoch: Self::DEFAULT_MINT_CAP_PER_EPOCH,
+            version: Self::CURRENT_VERSION,
+            ..Default::default()
+        }
+    }
+
+    pub fn verify_spl_token_type(&self, passed_spl_token_account_mint: Pubkey) -> bool {
+        passed_spl_token_account_mint == self.mint
+    }
+
+    /// Update the interest rate and accrual period, rejecting values outside the allowed bounds.
+    pub fn set_interest_params(
+        &mut self,
+        interest_rate_bps: u16,
+        accrual_period: i64,
+    ) -> Result<()> {
+        if interest_rate_bps > ProgramInfo::MAX_INTEREST_RATE_BPS
+            || !(ProgramInfo::MIN_ACCRUAL_PERIOD..=ProgramInfo::MAX_ACCRUAL_PERIOD)
+                .contains(&accrual_period)
+        {
+            return Err(VaultError::InvalidInterestParams.into());
+        }
+
+        self.interest_rate_bps = interest_rate_bps;
+        self.accrual_period = accrual_period;
+
+        Ok(())
+    }
+
+    /// Update the protocol fees on deposits and withdrawals.
+    pub fn set_fee_params(&mut self, deposit_fee_bps: u16, withdrawal_fee_bps: u16) -> Result<()> {
+        if deposit_fee_bps > ProgramInfo::MAX_FEE_BPS
+            || withdrawal_fee_bps > ProgramInfo::MAX_FEE_BPS
+        {
+            return Err(VaultError::InvalidFeeParams.into());
+        }
+
+        self.deposit_fee_bps = deposit_fee_bps;
+        self.withdrawal_fee_bps = withdrawal_fee_bps;
+
+        Ok(())
+    }
+
+    /// Protocol fee taken from a deposit of `amount`.
+    pub fn calculate_deposit_fee(&self, amount: u64) -> Result<u64> {
+        ProgramInfo::basis_points_of(amount, self.deposit_fee_bps)
+    }
+
+    /// Protocol fee taken from a withdrawal of `amount`.
+    pub fn calculate_withdrawal_fee(&self, amount: u64) -> Result<u64> {
+        ProgramInfo::basis_points_of(amount, self.withdrawal_fee_bps)
+    }
+
+    /// Update the deposit caps, a minimum deposit above either maximum would reject every deposit.
+    pub fn set_deposit_caps(
+        &mut self,
+        max_vault_balance: u64,
+        max_total_deposits: u64,
+        min_deposit: u64,
+    ) -> Result<()> {
+        if min_deposit > max_vault_balance || min_deposit > max_total_deposits {
+            return Err(VaultError::InvalidDepositCaps.into());
+        }
+
+        self.max_vault_balance = max_vault_balance;
+        self.max_total_deposits = max_total_deposits;
+        self.min_deposit = min_deposit;
+        Ok(())
+    }
+
+    /// Check a deposit of `amount` into a vault holding `vault_balance`, with the mint's vaults
+    /// holding `total_deposits`, against the caps. Interest is paid regardless of them, so it
+    /// can take balances past the maximums.
+    pub fn verify_deposit_caps(
+        &self,
+        total_deposits: u64,
+        vault_balance: u64,
+        amount: u64,
+    ) -> Result<()> {
+        if amount < self.min_deposit {
+            return Err(VaultError::DepositTooSmall.into());
+        }
+        if vault_balance.saturating_add(amount) > self.max_vault_balance {
+            return Err(VaultError::VaultBalanceCapExceeded.into());
+        }
+        if total_deposits.saturating_add(amount) > self.max_total_deposits {
+            return Err(VaultError::MintDepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Update the maximum amount of the mint that can be minted per epoch.
+    pub fn set_mint_cap(&mut self, mint_cap_per_epoch: u64) {
+        self.mint_cap_per_epoch = mint_cap_per_epoch;
+    }
+
+    /// Count `amount` against the mint cap of `epoch`, starting a fresh count on a new epoch.
+    pub fn record_mint(&mut self, amount: u64, epoch: u64) -> Result<()> {
+        if epoch != self.mint_epoch {
+            self.mint_epoch = epoch;
+            self.minted_in_epoch = 0;
+        }
+
+        let minted_in_epoch = self
+            .minted_in_epoch
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        if minted_in_epoch > self.mint_cap_per_epoch {
+            return Err(VaultError::MintCapExceeded.into());
+        }
+        self.minted_in_epoch = minted_in_epoch;
+
+        Ok(())
+    }
+
+    /// Grow the interest index by the interest earned since it was last accrued.
+    ///
+    /// The index compounds every time it is accrued, so touching it often approximates
+    /// continuous compounding at `interest_rate_bps` per accrual period.
+    pub fn accrue_interest(&mut self, current_time: i64) -> Result<()> {
+        let elapsed = current_time
+            .checked_sub(self.index_updated_at)
+            .ok_or(VaultError::MathOverflow)?;
+        if elapsed <= 0 {
+            return Ok(());
+        }
+
+        let index_growth = self
+            .interest_index
+            .checked_mul(self.interest_rate_bps as u128)
+            .and_then(|value| value.checked_mul(elapsed as u128))
+            .and_then(|value| {
+                value.checked_div(
+                    ProgramInfo::BASIS_POINTS_DIVISOR as u128 * self.accrual_period as u128,
+                )
+            })
+            .ok_or(VaultError::MathOverflow)?;
+        self.interest_index = self
+            .interest_index
+            .checked_add(index_growth)
+            .ok_or(VaultError::MathOverflow)?;
+        self.index_updated_at = current_time;
+
+        Ok(())
+    }
+
+    /// Number of shares `amount` tokens buy at the current index, rounded down.
+    pub fn amount_to_shares(&self, amount: u64) -> Result<u64> {
+        let shares = (amount as u128)
+            .checked_mul(Self::INTEREST_INDEX_SCALE)
+            .and_then(|value| value.checked_div(self.interest_index))
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(shares).map_err(|_| VaultError::MathOverflow)?)
+    }
+
+    /// Number of shares needed to redeem `amount` tokens at the current index, rounded up.
+    pub fn amount_to_shares_rounded_up(&self, amount: u64) -> Result<u64> {
+        let scaled_amount = (amount as u128)
+            .checked_mul(Self::INTEREST_INDEX_SCALE)
+            .ok_or(VaultError::MathOverflow)?;
+        let mut shares = scaled_amount
+            .checked_div(self.interest_index)
+            .ok_or(VaultError::MathOverflow)?;
+        if shares * self.interest_index < scaled_amount {
+            shares += 1;
+        }
+        Ok(u64::try_from(shares).map_err(|_| VaultError::MathOverflow)?)
+    }
+
+    /// Token value of `shares` at the current index, rounded down.
+    pub fn shares_to_amount(&self, shares: u64) -> Result<u64> {
+        let amount = (shares as u128)
+            .checked_mul(self.interest_index)
+            .map(|value| value / Self::INTEREST_INDEX_SCALE)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(amount).map_err(|_| VaultError::MathOverflow)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximum_space_matches_serialized_size() {
+        let mint_config = MintConfig::new(
+            Pubkey::new_unique(),
+            255,
+            6,
+            Pubkey::new_unique(),
+            1_700_000_000,
+        );
+
+        assert_eq!(
+            mint_config.try_to_vec().unwrap().len(),
+            MintConfig::MAXIMUM_SPACE
+        );
+    }
+
+    #[test]
+    fn mint_cap_is_counted_per_epoch() {
+        let mut mint_config = MintConfig::new(
+            Pubkey::new_unique(),
+            255,
+            6,
+            Pubkey::new_unique(),
+            1_700_000_000,
+        );
+        mint_config.set_mint_cap(1_000);
+
+        mint_config.record_mint(600, 10).unwrap();
+        assert!(mint_config.record_mint(401, 10).is_err());
+        mint_config.record_mint(400, 10).unwrap();
+
+        // The count starts over in the next epoch
+        mint_config.record_mint(1_000, 11).unwrap();
+        assert_eq!(mint_config.minted_in_epoch, 1_000);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::errors::VaultError;
+use crate::state::MintConfig;
+use anchor_lang::prelude::*;
+
+/// Running totals of the vaults of one mint, the interest round in progress for it, and the
+/// protocol fees taken from its deposits and withdrawals. Fees are held in the mint stats'
+/// associated token account until the admin collects them into the treasury.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintStats {
+    pub mint: Pubkey,             // 32
+    pub bump: u8,                 // 1
+    pub total_deposits: u64,      // 8, sum of the balances of the mint's vaults
+    pub total_shares: u64,        // 8, sum of the shares of the mint's vaults
+    pub round_started_at: i64,    // 8, 0 until the first round is started
+    pub round_reserve: u64,       // 8, admin vault balance when the round started
+    pub round_interest_owed: u64, // 8, interest owed to the mint's vaults when the round started
+    pub round_payout_ratio: u64,  // 8, share of the owed interest paid out, see PAYOUT_RATIO_SCALE
+    pub round_shortfall: u64,     // 8, claims the round's payouts could not cover, still owed
+    pub treasury: Pubkey,         // 32, token account fees are collected into, set by the admin
+    pub uncollected_fees: u64,    // 8, fees held in the fee account
+    pub collected_fees: u64,      // 8, fees collected into the treasury so far
+    pub version: u8,              // 1, layout version, see CURRENT_VERSION
+    reserved: [u8; 32],           // 32, room for new fields without a realloc
+}
+
+impl MintStats {
+    pub const MAXIMUM_SPACE: usize = 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 1 + 32;
+    pub const SEED: &'static [u8] = b"mint-stats";
+
+    /// Layout version of newly created mint stats.
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Fixed point scale of the payout ratio, the full owed interest is paid at this value.
+    pub const PAYOUT_RATIO_SCALE: u64 = 1_000_000_000;
+
+    pub fn new(mint: Pubkey, bump: u8) -> Self {
+        Self {
+            mint,
+            bump,
+            version: Self::CURRENT_VERSION,
+            ..Default::default()
+        }
+    }
+
+    pub fn record_deposit(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.total_deposits = self
+            .total_deposits
+            .checked_add(amount)
+            .ok_or(VaultError::BalanceOverflow)?;
+        self.total_shares = self
+            .total_shares
+            .checked_add(shares)
+            .ok_or(VaultError::BalanceOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.total_deposits = self
+            .total_deposits
+            .checked_sub(amount)
+            .ok_or(VaultError::BalanceUnderflow)?;
+        self.total_shares = self
+            .total_shares
+            .checked_sub(shares)
+            .ok_or(VaultError::BalanceUnderflow)?;
+        Ok(())
+    }
+
+    /// Call `f` with the seeds the mint stats PDA signs fee collections with.
+    pub fn with_signer_seeds<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        f(&[&[Self::SEED, self.mint.as_ref(), &[self.bump]]])
+    }
+
+    pub fn record_fee(&mut self, amount: u64) -> Result<()> {
+        self.uncollected_fees = self
+            .uncollected_fees
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Move the uncollected fees over to the collected total, returning how much to sweep.
+    pub fn collect_fees(&mut self) -> Result<u64> {
+        let amount = self.uncollected_fees;
+        self.collected_fees = self
+            .collected_fees
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.uncollected_fees = 0;
+        Ok(amount)
+    }
+
+    /// A round lasts one accrual period from when it was started.
+    pub fn is_round_active(&self, current_time: i64, accrual_period: i64) -> bool {
+        self.round_started_at != 0 && current_time < self.round_started_at + accrual_period
+    }
+
+    /// Snapshot the reserve against the interest owed to the mint's vaults, fixing the share
+    /// of it every vault is paid for the rest of the round.
+    pub fn start_round(
+        &mut self,
+        reserve: u64,
+        mint_config: &MintConfig,
+        current_time: i64,
+    ) -> Result<()> {
+        if self.is_round_active(current_time, mint_config.accrual_period) {
+            return Err(VaultError::InterestRoundInProgress.into());
+        }
+
+        let interest_owed = mint_config
+            .shares_to_amount(self.total_shares)?
+            .saturating_sub(self.total_deposits);
+        let payout_ratio = if reserve >= interest_owed {
+            Self::PAYOUT_RATIO_SCALE
+        } else {
+            // reserve < interest_owed, so the ratio is below the scale and fits
+            ((reserve as u128 * Self::PAYOUT_RATIO_SCALE as u128) / interest_owed as u128) as u64
+        };
+
+        self.round_started_at = current_time;
+        self.round_reserve = reserve;
+        self.round_interest_owed = interest_owed;
+        self.round_payout_ratio = payout_ratio;
+        self.round_shortfall = 0;
+        Ok(())
+    }
+
+    /// The part of `interest_amount` paid out during the current round, rounded down.
+    pub fn round_payout(&self, interest_amount: u64) -> Result<u64> {
+        let payout = (interest_amount as u128)
+            .checked_mul(self.round_payout_ratio as u128)
+            .map(|value| value / Self::PAYOUT_RATIO_SCALE as u128)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(payout).map_err(|_| VaultError::MathOverflow)?)
+    }
+
+    /// Swap a vault's earlier shortfall in this round for its current one, so each vault's
+    /// claim is only counted once however often it is paid.
+    pub fn replace_shortfall(&mut self, previous: u64, current: u64) -> Result<()> {
+        self.round_shortfall = self
+            .round_shortfall
+            .checked_sub(previous)
+            .and_then(|shortfall| shortfall.checked_add(current))
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximum_space_matches_serialized_size() {
+        let mint_stats = MintStats::new(Pubkey::new_unique(), 255);
+
+        assert_eq!(
+            mint_stats.try_to_vec().unwrap().len(),
+            MintStats::MAXIMUM_SPACE
+        );
+    }
+
+    /// Mint config whose index has grown 10% since the deposits were made.
+    fn mint_config() -> MintConfig {
+        let mut mint_config = MintConfig::new(
+            Pubkey::new_unique(),
+            255,
+            6,
+            Pubkey::new_unique(),
+            1_700_000_000,
+        );
+        mint_config.interest_index = MintConfig::INTEREST_INDEX_SCALE * 11 / 10;
+        mint_config
+    }
+
+    #[test]
+    fn short_reserve_pays_out_pro_rata() {
+        let mint_config = mint_config();
+        let mut mint_stats = MintStats::new(mint_config.mint, 255);
+        mint_stats.record_deposit(1_000, 1_000).unwrap();
+
+        // 100 is owed but the reserve only holds 40
+        mint_stats
+            .start_round(40, &mint_config, 1_700_000_000)
+            .unwrap();
+        assert_eq!(mint_stats.round_interest_owed, 100);
+        assert_eq!(mint_stats.round_reserve, 40);
+        assert_eq!(
+            mint_stats.round_payout_ratio,
+            MintStats::PAYOUT_RATIO_SCALE * 2 / 5
+        );
+
+        assert_eq!(mint_stats.round_payout(50).unwrap(), 20);
+        assert_eq!(mint_stats.round_payout(100).unwrap(), 40);
+        // Rounded down, so the reserve is never overdrawn
+        assert_eq!(mint_stats.round_payout(3).unwrap(), 1);
+
+        mint_stats.replace_shortfall(0, 30).unwrap();
+        mint_stats.replace_shortfall(0, 60).unwrap();
+        assert_eq!(mint_stats.round_shortfall, 90);
+        // Paying the first vault again replaces its shortfall rather than adding to it
+        mint_stats.replace_shortfall(30, 18).unwrap();
+        assert_eq!(mint_stats.round_shortfall, 78);
+    }
+
+    #[test]
+    fn funded_reserve_pays_out_in_full() {
+        let mint_config = mint_config();
+        let mut mint_stats = MintStats::new(mint_config.mint, 255);
+        mint_stats.record_deposit(1_000, 1_000).unwrap();
+
+        mint_stats
+            .start_round(500, &mint_config, 1_700_000_000)
+            .unwrap();
+        assert_eq!(mint_stats.round_payout_ratio, MintStats::PAYOUT_RATIO_SCALE);
+        assert_eq!(mint_stats.round_payout(100).unwrap(), 100);
+    }
+
+    #[test]
+    fn rounds_cannot_overlap() {
+        let mint_config = mint_config();
+        let mut mint_stats = MintStats::new(mint_config.mint, 255);
+        mint_stats.record_deposit(1_000, 1_000).unwrap();
+
+        mint_stats
+            .start_round(40, &mint_config, 1_700_000_000)
+            .unwrap();
+        mint_stats.replace_shortfall(0, 10).unwrap();
+        assert!(mint_stats
+            .start_round(
+                40,
+                &mint_config,
+                1_700_000_000 + mint_config.accrual_period - 1
+            )
+            .is_err());
+
+        // A new round starts once the period is over and clears the shortfall
+        mint_stats
+            .start_round(40, &mint_config, 1_700_000_000 + mint_config.accrual_period)
+            .unwrap();
+        assert_eq!(mint_stats.round_shortfall, 0);
+    }
+}
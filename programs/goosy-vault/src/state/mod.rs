@@ -1,5 +1,11 @@
+mod mint_config;
+mod mint_stats;
 mod program_info;
 mod vault;
+mod vault_authority;
 
+pub use mint_config::*;
+pub use mint_stats::*;
 pub use program_info::*;
 pub use vault::*;
+pub use vault_authority::*;
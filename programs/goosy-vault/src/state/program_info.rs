@@ -1,30 +1,190 @@
+use crate::errors::VaultError;
 use anchor_lang::prelude::*;
 
 #[account]
 #[derive(Default, Debug)]
 pub struct ProgramInfo {
-    pub admin: Pubkey,     // 32
-    pub bump: u8,          // 1
-    pub vaults_count: u32, // 8
+    pub admin: Pubkey,                     // 32
+    pub bump: u8,                          // 1
+    pub vaults_count: u32,                 // 4
+    pub pending_admin: Option<Pubkey>,     // 33, admin proposed by the current admin
+    pub paused: bool,                      // 1, stops deposits, interest and minting
+    pub withdrawals_paused: bool,          // 1, stops withdrawals
+    pub early_withdrawal_penalty_bps: u16, // 2, penalty on withdrawals from locked vaults, 0 rejects them
+    pub locked_interest_bonus_bps: u16,    // 2, extra interest paid to locked vaults
+    pub active_vaults_count: u32,          // 4, vaults that have not been closed
+    pub version: u8,                       // 1, layout version, see CURRENT_VERSION
+    reserved: [u8; 32],                    // 32, room for new fields without a realloc
 }
 
 impl ProgramInfo {
-    pub const MAXIMUM_SPACE: usize = 1 + 32 + 8;
+    pub const MAXIMUM_SPACE: usize = 32 + 1 + 4 + 33 + 1 + 1 + 2 + 2 + 4 + 1 + 32;
     pub const SEED: &'static str = "program_info";
 
-    pub const TOKEN_DECIMALS: u8 = 6;
+    /// Layout version of a newly initialised program info, see `migrate`.
+    pub const CURRENT_VERSION: u8 = 1;
+
+    pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
+    pub const DEFAULT_INTEREST_RATE_BPS: u16 = 100; // 1%
+    pub const MAX_INTEREST_RATE_BPS: u16 = 10_000; // 100%
+    pub const DEFAULT_ACCRUAL_PERIOD: i64 = 30 * 24 * 60 * 60; // Approximate number of seconds in a month
+    pub const MIN_ACCRUAL_PERIOD: i64 = 24 * 60 * 60; // 1 day
+    pub const MAX_ACCRUAL_PERIOD: i64 = 365 * 24 * 60 * 60; // 1 year
+
+    /// Longest lock a vault can be created with.
+    pub const MAX_LOCK_DURATION: i64 = 5 * 365 * 24 * 60 * 60; // 5 years
+
+    /// Highest protocol fee the admin can set on deposits or withdrawals.
+    pub const MAX_FEE_BPS: u16 = 1_000; // 10%
 
     pub fn new(bump: u8, admin: Pubkey, vaults_count: u32) -> Self {
         Self {
             bump,
             admin,
             vaults_count,
-            // ..Default::default()
+            pending_admin: None,
+            paused: false,
+            withdrawals_paused: false,
+            early_withdrawal_penalty_bps: 0,
+            locked_interest_bonus_bps: 0,
+            active_vaults_count: 0,
+            version: Self::CURRENT_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Upgrade a program info read from an older layout to the current version, returning
+    /// the version it was at.
+    ///
+    /// Version 0 is the baseline layout, which only held the admin, bump and vault count.
+    /// Every other field is set to its default, and all numbered vaults are counted as active
+    /// since the baseline could not close them.
+    pub fn migrate(&mut self) -> Result<u8> {
+        let previous_version = self.version;
+        if previous_version >= Self::CURRENT_VERSION {
+            return Err(VaultError::AlreadyMigrated.into());
+        }
+
+        *self = Self {
+            active_vaults_count: self.vaults_count,
+            ..Self::new(self.bump, self.admin, self.vaults_count)
+        };
+        Ok(previous_version)
+    }
+
+    /// Increment the vault count, and the active vault count with it.
+    pub fn increment_vaults_count(&mut self) -> Result<()> {
+        self.vaults_count = self
+            .vaults_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        self.active_vaults_count = self
+            .active_vaults_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Decrement the active vault count when a vault is closed. Vault numbers are never
+    /// reused, so `vaults_count` is left as is.
+    pub fn decrement_active_vaults_count(&mut self) -> Result<()> {
+        self.active_vaults_count = self
+            .active_vaults_count
+            .checked_sub(1)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Propose a new admin, who has to accept before the change takes effect.
+    pub fn propose_admin(&mut self, new_admin: Pubkey) {
+        self.pending_admin = Some(new_admin);
+    }
+
+    /// Hand the admin role over to the pending admin, returning the previous admin.
+    pub fn accept_admin(&mut self) -> Result<Pubkey> {
+        let new_admin = self
+            .pending_admin
+            .take()
+            .ok_or(VaultError::NoPendingAdmin)?;
+        let previous_admin = self.admin;
+        self.admin = new_admin;
+        Ok(previous_admin)
+    }
+
+    /// Pause or resume the program, withdrawals have their own switch.
+    pub fn set_paused(&mut self, paused: bool, withdrawals_paused: bool) {
+        self.paused = paused;
+        self.withdrawals_paused = withdrawals_paused;
+    }
+
+    /// Update the early withdrawal penalty and the interest bonus for locked vaults.
+    pub fn set_lock_params(
+        &mut self,
+        early_withdrawal_penalty_bps: u16,
+        locked_interest_bonus_bps: u16,
+    ) -> Result<()> {
+        if early_withdrawal_penalty_bps as u64 > Self::BASIS_POINTS_DIVISOR
+            || locked_interest_bonus_bps as u64 > Self::BASIS_POINTS_DIVISOR
+        {
+            return Err(VaultError::InvalidLockParams.into());
         }
+
+        self.early_withdrawal_penalty_bps = early_withdrawal_penalty_bps;
+        self.locked_interest_bonus_bps = locked_interest_bonus_bps;
+
+        Ok(())
+    }
+
+    /// Penalty withheld from `amount` withdrawn from a locked vault.
+    pub fn calculate_early_withdrawal_penalty(&self, amount: u64) -> Result<u64> {
+        Self::basis_points_of(amount, self.early_withdrawal_penalty_bps)
+    }
+
+    /// Extra interest a locked vault earns on top of `interest_amount`.
+    pub fn calculate_locked_interest_bonus(&self, interest_amount: u64) -> Result<u64> {
+        Self::basis_points_of(interest_amount, self.locked_interest_bonus_bps)
     }
 
-    /// Increment the vault count.
-    pub fn increment_vaults_count(&mut self) {
-        self.vaults_count += 1;
+    /// `basis_points` of `amount`, rounded down.
+    pub fn basis_points_of(amount: u64, basis_points: u16) -> Result<u64> {
+        let value = (amount as u128)
+            .checked_mul(basis_points as u128)
+            .map(|value| value / Self::BASIS_POINTS_DIVISOR as u128)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(value).map_err(|_| VaultError::MathOverflow)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximum_space_matches_serialized_size() {
+        let mut program_info = ProgramInfo::new(255, Pubkey::new_unique(), 0);
+        // The pending admin is the only variable sized field, the space covers it being set
+        program_info.propose_admin(Pubkey::new_unique());
+
+        assert_eq!(
+            program_info.try_to_vec().unwrap().len(),
+            ProgramInfo::MAXIMUM_SPACE
+        );
+    }
+
+    #[test]
+    fn migrates_the_first_layout() {
+        // The first layout only held the admin, bump and vault count
+        let mut data = ProgramInfo::new(255, Pubkey::new_unique(), 3)
+            .try_to_vec()
+            .unwrap();
+        data.truncate(32 + 1 + 4);
+        data.resize(ProgramInfo::MAXIMUM_SPACE, 0);
+
+        let mut migrated = ProgramInfo::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.migrate().unwrap(), 0);
+        assert_eq!(migrated.vaults_count, 3);
+        assert_eq!(migrated.active_vaults_count, 3);
+        assert_eq!(migrated.version, ProgramInfo::CURRENT_VERSION);
+        assert!(migrated.migrate().is_err());
     }
 }
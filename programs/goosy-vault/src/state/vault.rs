@@ -1,3 +1,5 @@
+use crate::errors::VaultError;
+use crate::state::{MintConfig, ProgramInfo};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -5,15 +7,34 @@ use anchor_lang::prelude::*;
 pub struct Vault {
     pub bump: u8,                  // 1
     pub owner: Pubkey,             // 32
-    total_balance: u64,            // 16
+    total_balance: u64,            // 8
     pub spl_token_account: Pubkey, // 32, SPL token account for this vault
     pub spl_token_mint: Pubkey,    // 32, Mint of the SPL token
-    pub vault_num: u32,            // 8
+    pub vault_num: u32,            // 4, the nonce for owner seeded vaults
     pub creation_date: i64,        // 8
+    pub last_interest_at: i64,     // 8, time interest was last paid to this vault
+    pub accrued_interest: u64,     // 8, total interest paid to this vault
+    pub shares: u64,               // 8, claim on the global interest index
+    pub decimals: u8,              // 1, decimals of the SPL token mint
+    pub unlock_at: i64,            // 8, withdrawals before this time are early, 0 if never locked
+    pub owner_seeded: bool,        // 1, seeded by owner, mint and nonce rather than the count
+    pub has_vault_authority: bool, // 1, co-signers and delegates are kept in a VaultAuthority
+    pub interest_shortfall: u64,   // 8, claim the reserve could not cover at the last round payout
+    pub version: u8,               // 1, layout version, see CURRENT_VERSION
+    pub fees_paid: u64,            // 8, protocol fees taken from deposits and withdrawals
+    reserved: [u8; 24],            // 24, room for new fields without a realloc
 }
 
 impl Vault {
-    pub const MAXIMUM_SPACE: usize = 1 + 16 + 32 + 32 + 32 + 8 + 8;
+    pub const MAXIMUM_SPACE: usize =
+        1 + 32 + 8 + 32 + 32 + 4 + 8 + 8 + 8 + 8 + 1 + 8 + 1 + 1 + 8 + 1 + 8 + 24;
+    pub const SEED: &'static [u8] = b"vault";
+    pub const OWNER_SEED: &'static [u8] = b"owner-vault";
+    pub const ADMIN_SEED: &'static [u8] = b"admin-vault";
+
+    /// Layout version of newly created vaults. Vaults created before the version byte was
+    /// added read as version 0 once `migrate_vault` has grown them to the current size.
+    pub const CURRENT_VERSION: u8 = 1;
 
     pub fn new(
         bump: u8,
@@ -21,6 +42,7 @@ impl Vault {
         vault_num: u32,
         spl_token_account: Pubkey,
         spl_token_mint: Pubkey,
+        decimals: u8,
         creation_date: i64,
     ) -> Self {
         Vault {
@@ -29,29 +51,260 @@ impl Vault {
             vault_num,
             spl_token_account,
             spl_token_mint,
+            decimals,
             creation_date,
+            last_interest_at: creation_date,
+            version: Self::CURRENT_VERSION,
             ..Default::default()
         }
     }
 
-    pub fn has_sufficient_balance(&self, amount: u64) -> bool {
-        self.total_balance >= amount
+    /// Upgrade a vault read from an older layout to the current version, returning the
+    /// version it was at.
+    ///
+    /// Version 0 is the baseline layout, which ended at the creation date, so every field
+    /// after it reads as zero. The balance is still held in the owner's token account and has
+    /// no shares, `take_vault_custody` moves it into the vault's custody and issues them.
+    pub fn migrate(&mut self, decimals: u8) -> Result<u8> {
+        let previous_version = self.version;
+        if previous_version >= Self::CURRENT_VERSION {
+            return Err(VaultError::AlreadyMigrated.into());
+        }
+
+        // Interest would otherwise be due for every period since the epoch
+        self.last_interest_at = self.creation_date;
+        self.decimals = decimals;
+
+        self.version = Self::CURRENT_VERSION;
+        Ok(previous_version)
+    }
+
+    /// Take custody of `balance` tokens moved into the vault's own `spl_token_account`,
+    /// replacing the balance recorded against the account it held before.
+    pub fn take_custody(&mut self, spl_token_account: Pubkey, balance: u64) {
+        self.spl_token_account = spl_token_account;
+        self.total_balance = balance;
     }
 
-    pub fn verify_spl_token_type(&self, passed_spl_token_account_mint: Pubkey) -> bool {
-        if passed_spl_token_account_mint != self.spl_token_mint {
-            return false;
+    /// Call `f` with the seeds the vault PDA signs with, which depend on how it was addressed.
+    pub fn with_signer_seeds<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let vault_num = self.vault_num.to_be_bytes();
+        let bump = [self.bump];
+        if self.owner_seeded {
+            f(&[&[
+                Self::OWNER_SEED,
+                self.owner.as_ref(),
+                self.spl_token_mint.as_ref(),
+                &vault_num,
+                &bump,
+            ]])
+        } else {
+            f(&[&[Self::SEED, &vault_num, &bump]])
         }
-        true
     }
 
-    pub fn update_balance_after_deposit(&mut self, amount: u64) {
-        self.total_balance += amount;
+    /// Whether `address` is this vault's PDA under either user vault scheme. Admin vaults are
+    /// derived from different seeds and never match.
+    pub fn is_user_vault_address(&self, address: &Pubkey, program_id: &Pubkey) -> bool {
+        self.with_signer_seeds(|signer_seeds| {
+            matches!(
+                Pubkey::create_program_address(signer_seeds[0], program_id),
+                Ok(derived) if derived == *address
+            )
+        })
+    }
+
+    /// Lock the vault for `lock_duration` seconds from its creation, making it a term deposit.
+    pub fn lock(&mut self, lock_duration: i64) -> Result<()> {
+        if !(0..=ProgramInfo::MAX_LOCK_DURATION).contains(&lock_duration) {
+            return Err(VaultError::InvalidLockDuration.into());
+        }
+
+        self.unlock_at = self
+            .creation_date
+            .checked_add(lock_duration)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn is_locked(&self, current_time: i64) -> bool {
+        current_time < self.unlock_at
     }
 
-    pub fn update_balance_after_withdrawal(&mut self, amount: u64) {
-        if self.total_balance >= amount {
-            self.total_balance -= amount;
+    /// Part of `interest_amount`, earned evenly since interest was last paid, that was earned
+    /// before the vault unlocked.
+    pub fn interest_earned_while_locked(
+        &self,
+        interest_amount: u64,
+        current_time: i64,
+    ) -> Result<u64> {
+        let elapsed = current_time.saturating_sub(self.last_interest_at);
+        if elapsed <= 0 {
+            return Ok(0);
         }
+        let locked_elapsed = current_time
+            .min(self.unlock_at)
+            .saturating_sub(self.last_interest_at)
+            .max(0);
+
+        let amount = (interest_amount as u128)
+            .checked_mul(locked_elapsed as u128)
+            .map(|value| value / elapsed as u128)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(amount).map_err(|_| VaultError::MathOverflow)?)
+    }
+
+    pub fn total_balance(&self) -> u64 {
+        self.total_balance
+    }
+
+    pub fn has_sufficient_balance(&self, amount: u64) -> bool {
+        self.total_balance >= amount
+    }
+
+    /// Whether a full accrual period has passed since interest was last paid.
+    pub fn is_interest_due(&self, current_time: i64, accrual_period: i64) -> bool {
+        current_time - self.last_interest_at >= accrual_period
+    }
+
+    /// Record an interest payout made at `current_time`.
+    pub fn record_interest_payout(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        self.accrued_interest = self
+            .accrued_interest
+            .checked_add(amount)
+            .ok_or(VaultError::BalanceOverflow)?;
+        self.last_interest_at = current_time;
+        Ok(())
+    }
+
+    /// The vault keeps its claim on what a round could not pay, this only records how much it is.
+    pub fn record_interest_shortfall(&mut self, amount: u64) {
+        self.interest_shortfall = amount;
+    }
+
+    pub fn record_fee(&mut self, amount: u64) -> Result<()> {
+        self.fees_paid = self
+            .fees_paid
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Interest the vault's shares have earned on top of its recorded balance.
+    pub fn pending_interest(&self, mint_config: &MintConfig) -> Result<u64> {
+        Ok(mint_config
+            .shares_to_amount(self.shares)?
+            .saturating_sub(self.total_balance))
+    }
+
+    pub fn update_balance_after_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_balance = self
+            .total_balance
+            .checked_add(amount)
+            .ok_or(VaultError::BalanceOverflow)?;
+        Ok(())
+    }
+
+    pub fn update_balance_after_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.total_balance = self
+            .total_balance
+            .checked_sub(amount)
+            .ok_or(VaultError::BalanceUnderflow)?;
+        Ok(())
+    }
+
+    /// Issue shares for `amount` tokens deposited at the current index, returning how many.
+    pub fn mint_shares(&mut self, amount: u64, mint_config: &MintConfig) -> Result<u64> {
+        let shares = mint_config.amount_to_shares(amount)?;
+        self.shares = self
+            .shares
+            .checked_add(shares)
+            .ok_or(VaultError::BalanceOverflow)?;
+        Ok(shares)
+    }
+
+    /// Redeem the shares backing `amount` tokens withdrawn at the current index.
+    ///
+    /// Rounding up can ask for a fraction of a share more than the vault holds when
+    /// it is emptied, so the burn is capped at the vault's shares. Returns how many were burned.
+    pub fn burn_shares(&mut self, amount: u64, mint_config: &MintConfig) -> Result<u64> {
+        let shares = mint_config
+            .amount_to_shares_rounded_up(amount)?
+            .min(self.shares);
+        self.shares -= shares;
+        Ok(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault() -> Vault {
+        Vault::new(
+            255,
+            Pubkey::new_unique(),
+            1,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            6,
+            1_700_000_000,
+        )
+    }
+
+    #[test]
+    fn maximum_space_matches_serialized_size() {
+        assert_eq!(vault().try_to_vec().unwrap().len(), Vault::MAXIMUM_SPACE);
+    }
+
+    #[test]
+    fn interest_earned_while_locked_stops_at_unlock() {
+        let mut vault = vault();
+        vault.lock(100).unwrap();
+
+        assert_eq!(
+            vault
+                .interest_earned_while_locked(1_000, 1_700_000_050)
+                .unwrap(),
+            1_000
+        );
+        // Half of the time since the last payout was before the vault unlocked
+        assert_eq!(
+            vault
+                .interest_earned_while_locked(1_000, 1_700_000_200)
+                .unwrap(),
+            500
+        );
+
+        vault.record_interest_payout(0, 1_700_000_150).unwrap();
+        assert_eq!(
+            vault
+                .interest_earned_while_locked(1_000, 1_700_000_300)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn migrates_the_baseline_layout() {
+        // The baseline layout ended at the creation date, the realloc zeroes everything after
+        let mut baseline = vault();
+        baseline.update_balance_after_deposit(500).unwrap();
+        let mut data = baseline.try_to_vec().unwrap();
+        data.truncate(1 + 32 + 8 + 32 + 32 + 4 + 8);
+        data.resize(Vault::MAXIMUM_SPACE, 0);
+
+        let mut migrated = Vault::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.version, 0);
+        assert_eq!(migrated.total_balance(), 500);
+        assert_eq!(migrated.last_interest_at, 0);
+        assert_eq!(migrated.decimals, 0);
+
+        assert_eq!(migrated.migrate(6).unwrap(), 0);
+        assert_eq!(migrated.version, Vault::CURRENT_VERSION);
+        assert_eq!(migrated.last_interest_at, migrated.creation_date);
+        assert_eq!(migrated.decimals, 6);
+        assert_eq!(migrated.shares, 0);
+        assert!(migrated.migrate(6).is_err());
     }
 }
@@ -0,0 +1,225 @@
+use crate::errors::VaultError;
+use anchor_lang::prelude::*;
+
+/// Key allowed to withdraw from a vault on the owner's behalf, up to a daily limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Delegate {
+    pub key: Pubkey,                 // 32
+    pub daily_withdrawal_limit: u64, // 8
+    pub withdrawn_today: u64,        // 8, amount withdrawn during `day`
+    pub day: i64,                    // 8, day `withdrawn_today` belongs to
+}
+
+impl Delegate {
+    pub const MAXIMUM_SPACE: usize = 32 + 8 + 8 + 8;
+}
+
+/// Companion account of a vault holding its co-signers and delegates.
+#[account]
+#[derive(Default, Debug)]
+pub struct VaultAuthority {
+    pub vault: Pubkey,                  // 32
+    pub bump: u8,                       // 1
+    pub co_signers: Vec<Pubkey>,        // 4 + 32 * MAX_CO_SIGNERS, sign alongside the owner
+    pub threshold: u8,                  // 1, signatures required from the owner and co-signers
+    pub multisig_threshold_amount: u64, // 8, a day's withdrawals above this need `threshold` signatures
+    pub delegates: Vec<Delegate>,       // 4 + Delegate::MAXIMUM_SPACE * MAX_DELEGATES
+    pub withdrawn_today: u64,           // 8, amount withdrawn by anyone during `day`
+    pub day: i64,                       // 8, day `withdrawn_today` belongs to
+}
+
+impl VaultAuthority {
+    pub const MAX_CO_SIGNERS: usize = 5;
+    pub const MAX_DELEGATES: usize = 5;
+    pub const MAXIMUM_SPACE: usize = 32
+        + 1
+        + (4 + 32 * Self::MAX_CO_SIGNERS)
+        + 1
+        + 8
+        + (4 + Delegate::MAXIMUM_SPACE * Self::MAX_DELEGATES)
+        + 8
+        + 8;
+    pub const SEED: &'static [u8] = b"vault-authority";
+
+    const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            vault,
+            bump,
+            threshold: 1,
+            multisig_threshold_amount: u64::MAX,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the co-signers and the number of signatures needed for large withdrawals.
+    /// The owner counts towards the threshold, so it can be at most one more than the
+    /// number of co-signers.
+    pub fn set_multisig(
+        &mut self,
+        vault_owner: &Pubkey,
+        co_signers: Vec<Pubkey>,
+        threshold: u8,
+        multisig_threshold_amount: u64,
+    ) -> Result<()> {
+        let has_duplicates = co_signers
+            .iter()
+            .enumerate()
+            .any(|(i, key)| key == vault_owner || co_signers[..i].contains(key));
+        if co_signers.len() > Self::MAX_CO_SIGNERS
+            || has_duplicates
+            || threshold == 0
+            || threshold as usize > co_signers.len() + 1
+        {
+            return Err(VaultError::InvalidVaultAuthority.into());
+        }
+
+        self.co_signers = co_signers;
+        self.threshold = threshold;
+        self.multisig_threshold_amount = multisig_threshold_amount;
+        Ok(())
+    }
+
+    /// Add or update a delegate, or remove it when `daily_withdrawal_limit` is `None`.
+    pub fn set_delegate(&mut self, key: Pubkey, daily_withdrawal_limit: Option<u64>) -> Result<()> {
+        let existing = self
+            .delegates
+            .iter()
+            .position(|delegate| delegate.key == key);
+        match (existing, daily_withdrawal_limit) {
+            (Some(index), Some(limit)) => self.delegates[index].daily_withdrawal_limit = limit,
+            (Some(index), None) => {
+                self.delegates.remove(index);
+            }
+            (None, Some(limit)) => {
+                if self.delegates.len() >= Self::MAX_DELEGATES {
+                    return Err(VaultError::TooManyDelegates.into());
+                }
+                self.delegates.push(Delegate {
+                    key,
+                    daily_withdrawal_limit: limit,
+                    ..Default::default()
+                });
+            }
+            (None, None) => return Err(VaultError::UnauthorizedDelegate.into()),
+        }
+        Ok(())
+    }
+
+    /// Check that `signers` hold enough of the owner and co-signer keys to meet the threshold.
+    pub fn verify_threshold(&self, vault_owner: &Pubkey, signers: &[Pubkey]) -> Result<()> {
+        let signatures = std::iter::once(vault_owner)
+            .chain(self.co_signers.iter())
+            .filter(|key| signers.contains(key))
+            .count();
+        if signatures < self.threshold as usize {
+            return Err(VaultError::NotEnoughSigners.into());
+        }
+        Ok(())
+    }
+
+    /// Authorize `authority` to withdraw `amount`. Delegates are held to their daily limit, and
+    /// once the day's withdrawals pass the multisig threshold they need `threshold` of
+    /// `signers`, so a large withdrawal cannot be split into small ones.
+    pub fn authorize_withdrawal(
+        &mut self,
+        vault_owner: &Pubkey,
+        authority: &Pubkey,
+        signers: &[Pubkey],
+        amount: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        if authority != vault_owner {
+            let day = current_time / Self::SECONDS_PER_DAY;
+            let delegate = self
+                .delegates
+                .iter_mut()
+                .find(|delegate| delegate.key == *authority)
+                .ok_or(VaultError::UnauthorizedDelegate)?;
+
+            if delegate.day != day {
+                delegate.day = day;
+                delegate.withdrawn_today = 0;
+            }
+            let withdrawn_today = delegate
+                .withdrawn_today
+                .checked_add(amount)
+                .ok_or(VaultError::MathOverflow)?;
+            if withdrawn_today > delegate.daily_withdrawal_limit {
+                return Err(VaultError::DelegateLimitExceeded.into());
+            }
+            delegate.withdrawn_today = withdrawn_today;
+        }
+
+        let day = current_time / Self::SECONDS_PER_DAY;
+        if self.day != day {
+            self.day = day;
+            self.withdrawn_today = 0;
+        }
+        let withdrawn_today = self
+            .withdrawn_today
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        if withdrawn_today > self.multisig_threshold_amount {
+            self.verify_threshold(vault_owner, signers)?;
+        }
+        self.withdrawn_today = withdrawn_today;
+        Ok(())
+    }
+}
+
+/// Keys that signed the transaction, `authority` and any signers passed as remaining accounts.
+pub fn signer_keys(authority: &Pubkey, remaining_accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    std::iter::once(*authority)
+        .chain(
+            remaining_accounts
+                .iter()
+                .filter(|account| account.is_signer)
+                .map(|account| account.key()),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximum_space_matches_serialized_size() {
+        let mut vault_authority = VaultAuthority::new(Pubkey::new_unique(), 255);
+        vault_authority.co_signers = vec![Pubkey::new_unique(); VaultAuthority::MAX_CO_SIGNERS];
+        vault_authority.delegates = vec![Delegate::default(); VaultAuthority::MAX_DELEGATES];
+
+        assert_eq!(
+            vault_authority.try_to_vec().unwrap().len(),
+            VaultAuthority::MAXIMUM_SPACE
+        );
+    }
+
+    #[test]
+    fn split_withdrawals_count_towards_the_multisig_threshold() {
+        let owner = Pubkey::new_unique();
+        let co_signer = Pubkey::new_unique();
+        let mut vault_authority = VaultAuthority::new(Pubkey::new_unique(), 255);
+        vault_authority
+            .set_multisig(&owner, vec![co_signer], 2, 100)
+            .unwrap();
+
+        let day = 1_700_000_000;
+        vault_authority
+            .authorize_withdrawal(&owner, &owner, &[owner], 60, day)
+            .unwrap();
+        assert!(vault_authority
+            .authorize_withdrawal(&owner, &owner, &[owner], 60, day)
+            .is_err());
+        vault_authority
+            .authorize_withdrawal(&owner, &owner, &[owner, co_signer], 60, day)
+            .unwrap();
+
+        // The running total starts over the next day
+        vault_authority
+            .authorize_withdrawal(&owner, &owner, &[owner], 60, day + 24 * 60 * 60)
+            .unwrap();
+    }
+}
@@ -2,9 +2,10 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { encode } from "@coral-xyz/anchor/dist/cjs/utils/bytes/utf8";
 import { GoosyVault } from "../target/types/goosy_vault";
-import { expect } from "chai";
+import { assert, expect } from "chai";
 import { describe } from "mocha";
 import {
+  BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
@@ -12,6 +13,8 @@ import {
   Transaction,
 } from "@solana/web3.js";
 import vault from "./suites/vault.test";
+import interest from "./suites/interest.test";
+import admin from "./suites/admin.test";
 
 describe("goosy-vault", () => {
   const provider = anchor.AnchorProvider.env();
@@ -46,6 +49,10 @@ describe("goosy-vault", () => {
 
   describe("Vault", vault.bind(this)); // execute the vault suite
 
+  describe("Interest", interest.bind(this)); // execute the interest suite
+
+  describe("Admin", admin.bind(this)); // execute the admin suite
+
   async function initializeProgramInfo(admin: Keypair) {
     const [newProgramInfoPDA, _bump] =
       anchor.web3.PublicKey.findProgramAddressSync(
@@ -58,11 +65,53 @@ describe("goosy-vault", () => {
     );
 
     if (programInfo === null) {
-      const _tx = await program.methods
+      // only the upgrade authority can initialise, it then hands the admin role over
+      const [programDataPDA] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+      );
+
+      // the authority check runs after the program info is created, so a payer other than the
+      // upgrade authority can only be seen to be turned away before it exists
+      try {
+        await program.methods
+          .initialiseProgramInfo()
+          .accounts({
+            payer: admin.publicKey,
+            programInfo: newProgramInfoPDA,
+            program: program.programId,
+            programData: programDataPDA,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("Initialising by a non-upgrade authority should have failed");
+      } catch (e: any) {
+        expect(e.error.errorCode.code).to.eql("NotUpgradeAuthority");
+      }
+
+      await program.methods
         .initialiseProgramInfo()
         .accounts({
-          payer: admin.publicKey,
+          payer: programWallet.publicKey,
+          programInfo: newProgramInfoPDA,
+          program: program.programId,
+          programData: programDataPDA,
+        })
+        .rpc();
+
+      await program.methods
+        .proposeAdmin(admin.publicKey)
+        .accounts({
+          programInfo: newProgramInfoPDA,
+          admin: programWallet.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .acceptAdmin()
+        .accounts({
           programInfo: newProgramInfoPDA,
+          newAdmin: admin.publicKey,
         })
         .signers([admin])
         .rpc();
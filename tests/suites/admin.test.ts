@@ -0,0 +1,165 @@
+import { Program } from "@coral-xyz/anchor";
+import {
+  BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+  Keypair,
+  PublicKey,
+} from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { GoosyVault } from "../../target/types/goosy_vault";
+import { Context } from "mocha";
+
+export default function suite() {
+  let program: Program<GoosyVault>,
+    admin: Keypair,
+    programInfoPDA: PublicKey,
+    generateFundedKeypair: () => Promise<Keypair>;
+
+  before(async function () {
+    ({ program, admin, programInfoPDA, generateFundedKeypair } = setupContext(
+      this.test.ctx
+    ));
+  });
+
+  it("Cannot initialise the program info twice", async () => {
+    const [programDataPDA] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+
+    // signed by the upgrade authority, so only the existing account stops it
+    try {
+      await program.methods
+        .initialiseProgramInfo()
+        .accounts({
+          payer: program.provider.publicKey,
+          programInfo: programInfoPDA,
+          program: program.programId,
+          programData: programDataPDA,
+        })
+        .rpc();
+      assert.fail("Initialising the program info again should have failed");
+    } catch (e: any) {
+      expect(e.logs.join("\n")).to.contain("already in use");
+    }
+  });
+
+  it("A non-admin cannot propose an admin", async () => {
+    const notAdmin = await generateFundedKeypair();
+
+    try {
+      await proposeAdmin(notAdmin, notAdmin.publicKey);
+      assert.fail("Proposing an admin by non-admin should have failed");
+    } catch (e: any) {
+      console.log(`Expected error due to non-admin proposal: ${e}`);
+    }
+  });
+
+  it("Only the pending admin can accept", async () => {
+    const newAdmin = await generateFundedKeypair();
+    const someoneElse = await generateFundedKeypair();
+
+    await proposeAdmin(admin, newAdmin.publicKey);
+
+    try {
+      await acceptAdmin(someoneElse);
+      assert.fail("Accepting by someone other than the pending admin should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("NotPendingAdmin");
+    }
+
+    const programInfo = await program.account.programInfo.fetch(programInfoPDA);
+    expect(programInfo.admin).to.eql(admin.publicKey);
+    expect(programInfo.pendingAdmin).to.eql(newAdmin.publicKey);
+  });
+
+  it("Admin can be transferred with propose and accept", async () => {
+    const newAdmin = await generateFundedKeypair();
+
+    await proposeAdmin(admin, newAdmin.publicKey);
+    await acceptAdmin(newAdmin);
+
+    let programInfo = await program.account.programInfo.fetch(programInfoPDA);
+    expect(programInfo.admin).to.eql(newAdmin.publicKey);
+    expect(programInfo.pendingAdmin).to.be.null;
+
+    // hand the role back so other suites keep working
+    await proposeAdmin(newAdmin, admin.publicKey);
+    await acceptAdmin(admin);
+
+    programInfo = await program.account.programInfo.fetch(programInfoPDA);
+    expect(programInfo.admin).to.eql(admin.publicKey);
+  });
+
+  it("Program info at the current version cannot be migrated again", async () => {
+    const programInfo = await program.account.programInfo.fetch(programInfoPDA);
+    expect(programInfo.version).to.eql(1);
+
+    try {
+      await program.methods
+        .migrateProgramInfo()
+        .accounts({
+          programInfo: programInfoPDA,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Migrating an up to date program info should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AlreadyMigrated");
+    }
+  });
+
+  it("A non-admin cannot migrate the program info", async () => {
+    const notAdmin = await generateFundedKeypair();
+
+    try {
+      await program.methods
+        .migrateProgramInfo()
+        .accounts({
+          programInfo: programInfoPDA,
+          admin: notAdmin.publicKey,
+        })
+        .signers([notAdmin])
+        .rpc();
+      assert.fail("Migrating the program info by non-admin should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("ConstraintHasOne");
+    }
+  });
+
+  /** Helper functions */
+  function setupContext(ctx: Context) {
+    const program = ctx.program;
+    const admin = ctx.admin;
+    const programInfoPDA = ctx.programInfoPDA;
+    const generateFundedKeypair = ctx.generateFundedKeypair;
+    return {
+      program,
+      admin,
+      programInfoPDA,
+      generateFundedKeypair,
+    };
+  }
+
+  async function proposeAdmin(signer: Keypair, newAdmin: PublicKey) {
+    await program.methods
+      .proposeAdmin(newAdmin)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function acceptAdmin(signer: Keypair) {
+    await program.methods
+      .acceptAdmin()
+      .accounts({
+        programInfo: programInfoPDA,
+        newAdmin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+}
@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { encode } from "@coral-xyz/anchor/dist/cjs/utils/bytes/utf8";
+import { assert, expect } from "chai";
+import { GoosyVault } from "../../target/types/goosy_vault";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Context } from "mocha";
+
+export default function suite() {
+  let program: Program<GoosyVault>,
+    admin: Keypair,
+    programInfoPDA: PublicKey,
+    generateFundedKeypair: () => Promise<Keypair>,
+    mint: PublicKey;
+
+  const defaultInterestRateBps = 100;
+  const defaultAccrualPeriod = 30 * 24 * 60 * 60;
+
+  before(async function () {
+    ({ program, admin, programInfoPDA, generateFundedKeypair } = setupContext(
+      this.test.ctx
+    ));
+
+    mint = await registerNewMint();
+  });
+
+  it("Registers the mint with its interest params", async () => {
+    const mintConfig = await fetchMintConfig();
+    expect(mintConfig.interestRateBps).to.eql(defaultInterestRateBps);
+    expect(mintConfig.accrualPeriod.toNumber()).to.eql(defaultAccrualPeriod);
+    expect(mintConfig.interestIndex.toString()).to.eql(
+      new BN(10).pow(new BN(12)).toString(),
+      "A new mint's index should start at one token per share"
+    );
+  });
+
+  it("Accrues the interest index", async () => {
+    const before = await fetchMintConfig();
+
+    await program.methods
+      .accrueInterest()
+      .accounts({ mint: mint, mintConfig: getMintConfigPDA() })
+      .rpc();
+
+    const after = await fetchMintConfig();
+    expect(after.interestIndex.gte(before.interestIndex)).to.be.true;
+    expect(after.indexUpdatedAt.toNumber()).to.be.gte(
+      before.indexUpdatedAt.toNumber()
+    );
+  });
+
+  it("Admin can update the interest params", async () => {
+    await setInterestParams(admin, 250, 7 * 24 * 60 * 60);
+
+    const mintConfig = await fetchMintConfig();
+    expect(mintConfig.interestRateBps).to.eql(250);
+    expect(mintConfig.accrualPeriod.toNumber()).to.eql(7 * 24 * 60 * 60);
+  });
+
+  it("Interest params are kept per mint", async () => {
+    const otherMint = await registerNewMint(500, 24 * 60 * 60);
+
+    const otherMintConfig = await fetchMintConfig(otherMint);
+    expect(otherMintConfig.interestRateBps).to.eql(500);
+    expect(otherMintConfig.accrualPeriod.toNumber()).to.eql(24 * 60 * 60);
+
+    const mintConfig = await fetchMintConfig();
+    expect(mintConfig.interestRateBps).to.eql(
+      250,
+      "Registering another mint should not change this mint's rate"
+    );
+  });
+
+  it("A non-admin cannot update the interest params", async () => {
+    const notAdmin = await generateFundedKeypair();
+
+    try {
+      await setInterestParams(notAdmin, 500, defaultAccrualPeriod);
+      assert.fail("Updating interest params by non-admin should have failed");
+    } catch (e: any) {
+      console.log(`Expected error due to non-admin update: ${e}`);
+    }
+  });
+
+  it("Rejects out of range interest params", async () => {
+    try {
+      await setInterestParams(admin, 10_001, defaultAccrualPeriod);
+      assert.fail("Interest rate above 100% should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidInterestParams");
+    }
+
+    try {
+      await setInterestParams(admin, defaultInterestRateBps, 60);
+      assert.fail("Accrual period below a day should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidInterestParams");
+    }
+  });
+
+  /** Helper functions */
+  function setupContext(ctx: Context) {
+    const program = ctx.program;
+    const admin = ctx.admin;
+    const programInfoPDA = ctx.programInfoPDA;
+    const generateFundedKeypair = ctx.generateFundedKeypair;
+    return {
+      program,
+      admin,
+      programInfoPDA,
+      generateFundedKeypair,
+    };
+  }
+
+  function getMintConfigPDA(configMint: PublicKey = mint) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("mint-config"), configMint.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function fetchMintConfig(configMint: PublicKey = mint) {
+    return program.account.mintConfig.fetch(getMintConfigPDA(configMint));
+  }
+
+  // creates a mint with its admin vault and registers it
+  async function registerNewMint(
+    interestRateBps = defaultInterestRateBps,
+    accrualPeriod = defaultAccrualPeriod
+  ) {
+    const connection = program.provider.connection;
+    const newMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6
+    );
+    const [adminVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("admin-vault"), newMint.toBuffer()],
+      program.programId
+    );
+    const [mintStats] = anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("mint-stats"), newMint.toBuffer()],
+      program.programId
+    );
+    const adminVaultSplTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      newMint,
+      adminVault,
+      true
+    );
+
+    await program.methods
+      .createAdminVault()
+      .accounts({
+        vault: adminVault,
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: newMint,
+        splTokenAccount: adminVaultSplTokenAccount.address,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .registerMint(interestRateBps, new BN(accrualPeriod))
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: newMint,
+        adminVault: adminVault,
+        mintConfig: getMintConfigPDA(newMint),
+        mintStats: mintStats,
+        feeSplTokenAccount: getAssociatedTokenAddressSync(
+          newMint,
+          mintStats,
+          true
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return newMint;
+  }
+
+  async function setInterestParams(
+    signer: Keypair,
+    interestRateBps: number,
+    accrualPeriod: number
+  ) {
+    await program.methods
+      .setInterestParams(interestRateBps, new BN(accrualPeriod))
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: signer.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+      })
+      .signers([signer])
+      .rpc();
+  }
+}
@@ -7,9 +7,14 @@ import { toBytesInt32 } from "../utils/toBytesInt32";
 import { GoosyVault } from "../../target/types/goosy_vault";
 import {
   Account,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
   getMint,
   getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { Context } from "mocha";
@@ -22,7 +27,7 @@ export default function suite() {
     admin: Keypair,
     mint: PublicKey;
 
-  let vaultPDA: PublicKey, vaultOwner: Keypair, vaultSplTokenAccount: Account;
+  let vaultPDA: PublicKey, vaultOwner: Keypair, vaultSplTokenAccount: PublicKey;
 
   let adminSplTokenAccount: Account,
     adminVaultPDA: PublicKey,
@@ -32,13 +37,16 @@ export default function suite() {
   let testVaultOwner: Keypair, testVaultSplTokenAccount: Account;
 
   const tokenDecimals = 6;
+  const U64_MAX = new BN("18446744073709551615");
 
   before(async function () {
     ({ program, connection, admin, programInfoPDA, generateFundedKeypair } =
       setupContext(this.test.ctx));
 
-    adminVaultPDA = getAdminVaultPDA()[0];
-    mint = await mintToken(admin, 6); // 6 decimal places for token i.e. 1 SPL token = 1000000 smallest units
+    // the admin vault is keyed by its mint and is also the mint authority
+    const mintKeypair = Keypair.generate();
+    adminVaultPDA = getAdminVaultPDA(mintKeypair.publicKey)[0];
+    mint = await mintToken(admin, 6, mintKeypair); // 6 decimal places for token i.e. 1 SPL token = 1000000 smallest units
 
     try {
       ({ vaultPDA, vaultOwner, vaultSplTokenAccount, adminSplTokenAccount } =
@@ -49,6 +57,7 @@ export default function suite() {
     }
 
     await createAdminVault();
+    await registerMint();
 
     await preFundAdminVault();
   });
@@ -68,6 +77,15 @@ export default function suite() {
     const vault = await createVault(vaultOwner);
     expect(vault.owner).to.eql(vaultOwner.publicKey);
     expect(vault.totalBalance.toNumber()).to.eql(0);
+    expect(vault.lastInterestAt.toNumber()).to.eql(
+      vault.creationDate.toNumber(),
+      "Interest accrual should start from the vault's creation date"
+    );
+    expect(vault.accruedInterest.toNumber()).to.eql(0);
+    expect(vault.decimals).to.eql(
+      tokenDecimals,
+      "Vault should record the decimals of its mint"
+    );
   });
 
   it("Is updating the number of vaults", async () => {
@@ -81,142 +99,1804 @@ export default function suite() {
     );
   });
 
-  it("User can deposit tokens to vault", async () => {
+  it("Vaults are created at the current version", async () => {
+    const vault = await createVault(vaultOwner);
+    expect(vault.version).to.eql(1);
+
+    try {
+      await migrateVault(vaultPDA);
+      assert.fail("Migrating an up to date vault should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AlreadyMigrated");
+    }
+  });
+
+  it("Baseline vaults are migrated by the admin and taken into custody by their owner", async () => {
+    // tests/fixtures holds a baseline vault recording 500 tokens kept in its owner's token account
+    const legacyVaultOwner = Keypair.fromSeed(
+      encode("legacy-vault-owner-fixture-seed!")
+    );
+    const legacyVault = new PublicKey(
+      "CvGq7pcLRD2RJmTNJDg6T4obMdVJ9KUHbauwUdrRm6z2"
+    );
+    const legacyMint = new PublicKey(
+      "CTjb3SgbsyUNjwNRbtSW3wNMtHLuUX6oLjB5grk1b6Dq"
+    );
+    const legacySplTokenAccount = new PublicKey(
+      "GH1oKfG1anG2ZpgWhwcmWggA8m9a9mjibnv6hqVcvhAM"
+    );
+    const splTokenAccount = getAssociatedTokenAddressSync(
+      legacyMint,
+      legacyVault,
+      true
+    );
+
+    await (program.provider as anchor.AnchorProvider).sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: program.provider.publicKey,
+          toPubkey: legacyVaultOwner.publicKey,
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+        })
+      )
+    );
+
+    const legacyAdminSplTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      legacyMint,
+      getAdminVaultPDA(legacyMint)[0],
+      true
+    );
+    await createAdminVault(legacyMint, legacyAdminSplTokenAccount.address);
+    await registerMint(legacyMint);
+
+    // the baseline layout cannot be read until it has been migrated
+    try {
+      await takeVaultCustody(
+        legacyVault,
+        legacyVaultOwner,
+        legacySplTokenAccount,
+        splTokenAccount,
+        legacyMint
+      );
+      assert.fail("Taking custody of an unmigrated vault should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AccountDidNotDeserialize");
+    }
+
+    try {
+      await migrateVault(legacyVault, legacyMint, legacyVaultOwner);
+      assert.fail("Migrating by a non-admin should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("ConstraintHasOne");
+    }
+
+    await migrateVault(legacyVault, legacyMint);
+    let vault = await program.account.vault.fetch(legacyVault);
+    expect(vault.version).to.eql(1);
+    expect(vault.decimals).to.eql(6);
+    expect(vault.shares.toNumber()).to.eql(0);
+    expect(vault.splTokenAccount).to.eql(legacySplTokenAccount);
+
+    try {
+      await migrateVault(legacyVault, legacyMint);
+      assert.fail("Migrating a vault twice should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AlreadyMigrated");
+    }
+
+    await takeVaultCustody(
+      legacyVault,
+      legacyVaultOwner,
+      legacySplTokenAccount,
+      splTokenAccount,
+      legacyMint
+    );
+    vault = await program.account.vault.fetch(legacyVault);
+    expect(vault.splTokenAccount).to.eql(splTokenAccount);
+    expect(vault.totalBalance.toNumber()).to.eql(500);
+    expect(vault.shares.toNumber()).to.be.greaterThan(0);
+    expect(
+      Number((await getAccount(connection, splTokenAccount)).amount)
+    ).to.eql(500);
+    expect(
+      Number((await getAccount(connection, legacySplTokenAccount)).amount)
+    ).to.eql(0);
+
+    const mintStats = await program.account.mintStats.fetch(
+      getMintStatsPDA(legacyMint)
+    );
+    expect(mintStats.totalShares.toString()).to.eql(
+      vault.shares.toString()
+    );
+
+    try {
+      await takeVaultCustody(
+        legacyVault,
+        legacyVaultOwner,
+        splTokenAccount,
+        splTokenAccount,
+        legacyMint
+      );
+      assert.fail("Taking custody twice should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AlreadyInCustody");
+    }
+  });
+
+  it("User can deposit tokens to vault", async () => {
+    await createVault(vaultOwner);
+
+    const amountToDeposit = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amountToDeposit
+    );
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(
+      100,
+      "Vault's total balance should be 100 after deposit"
+    );
+
+    const vaultSplTokenAccountBalance = await connection.getTokenAccountBalance(
+      vaultSplTokenAccount
+    );
+    console.log(
+      `Vault SPL token account balance after deposit: ${vaultSplTokenAccountBalance.value.uiAmount}`
+    );
+    expect(vaultSplTokenAccountBalance.value.uiAmount).to.eql(
+      100 / 10 ** 6,
+      "On-chain token account balance should match the deposited amount"
+    );
+  });
+
+  it("Emits a Deposited event", async () => {
+    await createVault(vaultOwner);
+
+    let depositedEvent: any;
+    const listener = program.addEventListener("Deposited", (event) => {
+      depositedEvent = event;
+    });
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(100)
+    );
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(depositedEvent.vault).to.eql(vaultPDA);
+    expect(depositedEvent.owner).to.eql(vaultOwner.publicKey);
+    expect(depositedEvent.mint).to.eql(mint);
+    expect(depositedEvent.amount.toNumber()).to.eql(100);
+    expect(depositedEvent.balance.toNumber()).to.eql(100);
+  });
+
+  it("Deposits on behalf of another user carry the depositor and memo", async () => {
+    await createVault(vaultOwner);
+
+    // a company funds the vault from its own token account while a relayer pays the fees
+    const company = await generateFundedKeypair();
+    const relayer = await generateFundedKeypair();
+    const companySplTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      mint,
+      company.publicKey
+    );
+
+    let depositedEvent: any;
+    const listener = program.addEventListener("Deposited", (event) => {
+      depositedEvent = event;
+    });
+
+    // the relayer pays for the transaction without being an account of the instruction
+    await fundAccount(companySplTokenAccount.address, 1000 * 10 ** 6);
+    const transaction = await depositInstruction(
+      companySplTokenAccount.address,
+      vaultSplTokenAccount,
+      company,
+      new BN(100),
+      "payroll-2026-10"
+    ).transaction();
+    transaction.feePayer = relayer.publicKey;
+    await anchor.web3.sendAndConfirmTransaction(connection, transaction, [
+      relayer,
+      company,
+    ]);
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(depositedEvent.owner).to.eql(vaultOwner.publicKey);
+    expect(depositedEvent.depositor).to.eql(company.publicKey);
+    expect(depositedEvent.memo).to.eql("payroll-2026-10");
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(100);
+  });
+
+  it("Deposits with an overlong memo are rejected", async () => {
+    await createVault(vaultOwner);
+
+    try {
+      await depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        new BN(100),
+        "x".repeat(65)
+      );
+      assert.fail("A memo longer than 64 bytes should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("MemoTooLong");
+    }
+  });
+
+  it("Empty deposits are rejected", async () => {
+    await createVault(vaultOwner);
+
+    // they would let anyone pay out a vault's interest whenever they like
+    try {
+      await depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        new BN(0)
+      );
+      assert.fail("A deposit of nothing should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("DepositTooSmall");
+    }
+  });
+
+  it("Deposits issue shares against the interest index", async () => {
+    await createVault(vaultOwner);
+
+    const amountToDeposit = new BN(1000);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amountToDeposit
+    );
+
+    const mintConfig = await program.account.mintConfig.fetch(
+      getMintConfigPDA()
+    );
+    const vault = await program.account.vault.fetch(vaultPDA);
+    const expectedShares = amountToDeposit
+      .mul(new BN(10).pow(new BN(12)))
+      .div(mintConfig.interestIndex);
+    expect(vault.shares.toString()).to.eql(
+      expectedShares.toString(),
+      "Vault shares should be the deposit divided by the interest index"
+    );
+  });
+
+  it("User can withdraw funds", async () => {
+    await createVault(vaultOwner);
+
+    const amount = new BN(100);
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      amount
+    );
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(
+      0,
+      "Vault's total balance should be 0 after withdrawal"
+    );
+  });
+
+  it("A user cannot withdraw more than the vault holds", async () => {
+    await createVault(vaultOwner);
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(100)
+    );
+
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        new BN(101)
+      );
+      assert.fail("Withdrawing more than the vault balance should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InsufficientBalance");
+    }
+  });
+
+  it("Vault token account is owned by the vault PDA", async () => {
+    const vault = await createVault(vaultOwner);
+    expect(vault.splTokenAccount).to.eql(vaultSplTokenAccount);
+
+    const tokenAccount = await getAccount(connection, vaultSplTokenAccount);
+    expect(tokenAccount.owner).to.eql(
+      vaultPDA,
+      "The vault PDA should be the authority of its token account"
+    );
+  });
+
+  it("Vault creation succeeds when its token account was created first", async () => {
+    // anyone can derive the next vault's token account and create it ahead of the vault
+    const squatter = await generateFundedKeypair();
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      squatter,
+      mint,
+      vaultPDA,
+      true
+    );
+
+    const vault = await createVault(vaultOwner);
+    expect(vault.splTokenAccount).to.eql(vaultSplTokenAccount);
+
+    const tokenAccount = await getAccount(connection, vaultSplTokenAccount);
+    expect(tokenAccount.owner).to.eql(vaultPDA);
+  });
+
+  it("Mint registration succeeds when its fee account was created first", async () => {
+    const otherMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      tokenDecimals
+    );
+    const [otherAdminVault] = getAdminVaultPDA(otherMint);
+    const otherAdminSplTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      otherMint,
+      otherAdminVault,
+      true
+    );
+
+    // the fee account is derived from the mint stats, which anyone can derive ahead of them
+    const squatter = await generateFundedKeypair();
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      squatter,
+      otherMint,
+      getMintStatsPDA(otherMint),
+      true
+    );
+
+    await createAdminVault(otherMint, otherAdminSplTokenAccount.address);
+    await registerMint(otherMint);
+
+    const mintStats = await program.account.mintStats.fetch(
+      getMintStatsPDA(otherMint)
+    );
+    expect(mintStats.mint).to.eql(otherMint);
+  });
+
+  it("Mint stats are created at the current version", async () => {
+    const mintStats = await program.account.mintStats.fetch(getMintStatsPDA());
+    expect(mintStats.version).to.eql(1);
+  });
+
+  it("User can deposit Token-2022 tokens to vault", async () => {
+    const mintAuthority = await generateFundedKeypair();
+    const token2022Mint = await createMint(
+      connection,
+      mintAuthority,
+      mintAuthority.publicKey,
+      null,
+      tokenDecimals,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // each mint is backed by its own admin vault and registered before vaults can hold it
+    const [token2022AdminVault] = getAdminVaultPDA(token2022Mint);
+    const adminToken2022Account = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      token2022Mint,
+      token2022AdminVault,
+      true,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await createAdminVault(token2022Mint, adminToken2022Account.address);
+    await registerMint(token2022Mint, TOKEN_2022_PROGRAM_ID);
+
+    const vaultToken2022Account = getAssociatedTokenAddressSync(
+      token2022Mint,
+      vaultPDA,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .createVault(null)
+      .accounts({
+        vault: vaultPDA,
+        programInfo: programInfoPDA,
+        owner: vaultOwner.publicKey,
+        mint: token2022Mint,
+        mintConfig: getMintConfigPDA(token2022Mint),
+        splTokenAccount: vaultToken2022Account,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([vaultOwner])
+      .rpc();
+
+    const userToken2022Account = await getOrCreateAssociatedTokenAccount(
+      connection,
+      testVaultOwner,
+      token2022Mint,
+      testVaultOwner.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      connection,
+      mintAuthority,
+      token2022Mint,
+      userToken2022Account.address,
+      mintAuthority,
+      1000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .depositSplTokens(new BN(100), null)
+      .accounts({
+        userVault: vaultPDA,
+        depositor: testVaultOwner.publicKey,
+        userSplTokenAccount: userToken2022Account.address,
+        destinationVaultSplTokenAccount: vaultToken2022Account,
+        mint: token2022Mint,
+        mintConfig: getMintConfigPDA(token2022Mint),
+        mintStats: getMintStatsPDA(token2022Mint),
+        feeSplTokenAccount: getFeeTokenAccount(
+          token2022Mint,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        programInfo: programInfoPDA,
+        adminVault: token2022AdminVault,
+        adminVaultSplTokenAccount: adminToken2022Account.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([testVaultOwner])
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(
+      100,
+      "Vault's total balance should be 100 after deposit"
+    );
+  });
+
+  it("A user cannot withdraw someone else's funds", async () => {
+    await createVault(vaultOwner);
+
+    const amountToDeposit = new BN(100);
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amountToDeposit
+    );
+
+    let vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(100);
+
+    try {
+      await program.methods
+        .withdrawSplTokens(amountToDeposit)
+        .accounts({
+          vault: vaultPDA,
+          authority: vaultOwner.publicKey,
+          vaultAuthority: null,
+          payer: testVaultOwner.publicKey,
+          userSplTokenAccount: testVaultSplTokenAccount.address,
+          vaultSplTokenAccount: vaultSplTokenAccount,
+          mint: mint,
+          mintConfig: getMintConfigPDA(),
+          mintStats: getMintStatsPDA(),
+          feeSplTokenAccount: getFeeTokenAccount(),
+          programInfo: programInfoPDA,
+          adminVault: adminVaultPDA,
+          adminVaultSplTokenAccount: adminSplTokenAccount.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([testVaultOwner])
+        .rpc();
+
+      assert.fail("Withdrawal by non-owner should have failed");
+    } catch (e: any) {
+      console.log(`Expected error due to non-owner withdrawal: ${e}`);
+    }
+  });
+
+  it("Deposits are rejected while paused but withdrawals are not", async () => {
+    await createVault(vaultOwner);
+
+    const amount = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    await setPaused(true, false);
+    try {
+      try {
+        await depositSPLTokens(
+          testVaultSplTokenAccount.address,
+          vaultSplTokenAccount,
+          testVaultOwner,
+          amount
+        );
+        assert.fail("Deposit while paused should have failed");
+      } catch (e: any) {
+        expect(e.error.errorCode.code).to.eql("ProgramPaused");
+      }
+
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        amount
+      );
+    } finally {
+      await setPaused(false, false);
+    }
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(
+      0,
+      "Withdrawals should still be allowed while the program is paused"
+    );
+  });
+
+  it("Withdrawals are rejected while withdrawals are paused", async () => {
+    await createVault(vaultOwner);
+
+    const amount = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    await setPaused(false, true);
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        amount
+      );
+      assert.fail("Withdrawal while withdrawals are paused should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("WithdrawalsPaused");
+    } finally {
+      await setPaused(false, false);
+    }
+  });
+
+  it("Creates a locked Vault", async () => {
+    const lockDuration = new BN(7 * 24 * 60 * 60); // 1 week
+    const vault = await createVault(
+      vaultOwner,
+      vaultSplTokenAccount,
+      lockDuration
+    );
+
+    expect(vault.unlockAt.toNumber()).to.eql(
+      vault.creationDate.add(lockDuration).toNumber(),
+      "Vault should unlock once the lock duration has passed"
+    );
+  });
+
+  it("Withdrawal fees are taken after the early withdrawal penalty", async () => {
+    await createVault(vaultOwner, vaultSplTokenAccount, new BN(60 * 60));
+
+    const amount = new BN(1000);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    // the whole amount goes to the penalty, leaving nothing to take a fee from
+    await setLockParams(10_000, 0);
+    await setFeeParams(0, 200);
+    try {
+      const userBalanceBefore = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        amount
+      );
+
+      const userBalanceAfter = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+      expect(
+        (userBalanceAfter.amount - userBalanceBefore.amount).toString()
+      ).to.eql("0", "The full penalty should leave nothing for the user");
+    } finally {
+      await setLockParams(0, 0);
+      await setFeeParams(0, 0);
+    }
+  });
+
+  it("Early withdrawals are rejected when there is no penalty", async () => {
+    await createVault(vaultOwner, vaultSplTokenAccount, new BN(60 * 60));
+
+    const amount = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        amount
+      );
+      assert.fail("Withdrawal from a locked vault should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("VaultLocked");
+    }
+  });
+
+  it("Early withdrawals pay a penalty to the admin vault", async () => {
+    await createVault(vaultOwner, vaultSplTokenAccount, new BN(60 * 60));
+
+    const amount = new BN(1000);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    await setLockParams(500, 0); // 5% penalty
+    try {
+      const userBalanceBefore = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+      const adminBalanceBefore = await getAccount(
+        connection,
+        adminSplTokenAccount.address
+      );
+
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        amount
+      );
+
+      const userBalanceAfter = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+      const adminBalanceAfter = await getAccount(
+        connection,
+        adminSplTokenAccount.address
+      );
+
+      expect(
+        (userBalanceAfter.amount - userBalanceBefore.amount).toString()
+      ).to.eql("950", "User should receive the amount minus the penalty");
+      expect(
+        (adminBalanceAfter.amount - adminBalanceBefore.amount).toString()
+      ).to.eql("50", "Admin vault should receive the penalty");
+    } finally {
+      await setLockParams(0, 0);
+    }
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(
+      0,
+      "Vault's total balance should be 0 after withdrawal"
+    );
+  });
+
+  it("Creates a Vault addressed by owner, mint and nonce", async () => {
+    const nonce = 7;
+    const programInfoBefore = await program.account.programInfo.fetch(
+      programInfoPDA
+    );
+
+    // the helpers below act on vaultPDA, point them at the owner seeded vault
+    vaultPDA = findOwnerVaultPDA(vaultOwner.publicKey, mint, nonce);
+    vaultSplTokenAccount = getAssociatedTokenAddressSync(mint, vaultPDA, true);
+
+    await program.methods
+      .createOwnerVault(nonce, null)
+      .accounts({
+        vault: vaultPDA,
+        owner: vaultOwner.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        splTokenAccount: vaultSplTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([vaultOwner])
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.ownerSeeded).to.be.true;
+    expect(vault.vaultNum).to.eql(nonce);
+
+    const programInfoAfter = await program.account.programInfo.fetch(
+      programInfoPDA
+    );
+    expect(programInfoAfter.vaultsCount).to.eql(
+      programInfoBefore.vaultsCount,
+      "Owner seeded vaults should not use the vault count"
+    );
+
+    // the vault PDA signs for withdrawals and closing with its own seeds
+    const amount = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      amount
+    );
+    await closeVault(vaultOwner);
+
+    expect(await program.account.vault.fetchNullable(vaultPDA)).to.be.null;
+  });
+
+  it("Owner can close an empty vault", async () => {
+    await createVault(vaultOwner);
+
+    const amount = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amount
+    );
+
+    try {
+      await closeVault(vaultOwner);
+      assert.fail("Closing a vault that holds funds should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("VaultNotEmpty");
+    }
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      amount
+    );
+
+    const programInfoBefore = await program.account.programInfo.fetch(
+      programInfoPDA
+    );
+    await closeVault(vaultOwner);
+    const programInfoAfter = await program.account.programInfo.fetch(
+      programInfoPDA
+    );
+
+    expect(await program.account.vault.fetchNullable(vaultPDA)).to.be.null;
+    expect(await connection.getAccountInfo(vaultSplTokenAccount)).to.be.null;
+    expect(programInfoAfter.activeVaultsCount).to.eql(
+      programInfoBefore.activeVaultsCount - 1,
+      "Active vault count should decrease when a vault is closed"
+    );
+    expect(programInfoAfter.vaultsCount).to.eql(
+      programInfoBefore.vaultsCount,
+      "Vault numbers should not be reused"
+    );
+  });
+
+  it("Tokens sent to an empty vault do not stop it from being closed", async () => {
+    await createVault(vaultOwner);
+
+    // anyone can send tokens straight to the vault's token account
+    await mintTo(connection, admin, mint, vaultSplTokenAccount, admin, 5);
+    const userBalanceBefore = (
+      await getAccount(connection, testVaultSplTokenAccount.address)
+    ).amount;
+
+    await closeVault(vaultOwner);
+
+    expect(await program.account.vault.fetchNullable(vaultPDA)).to.be.null;
+    const userBalanceAfter = (
+      await getAccount(connection, testVaultSplTokenAccount.address)
+    ).amount;
+    expect((userBalanceAfter - userBalanceBefore).toString()).to.eql(
+      "5",
+      "Tokens sent without a deposit should be handed back on close"
+    );
+  });
+
+  it("A non-owner cannot close a vault", async () => {
+    await createVault(vaultOwner);
+
+    try {
+      await closeVault(testVaultOwner);
+      assert.fail("Closing by non-owner should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidVaultOwner");
+    }
+  });
+
+  it("Delegates can withdraw up to their daily limit", async () => {
+    await createVault(vaultOwner);
+    const vaultAuthority = await createVaultAuthority(
+      vaultOwner,
+      [],
+      1,
+      new BN("18446744073709551615")
+    );
+
+    const delegate = await generateFundedKeypair();
+    await setVaultDelegate(vaultOwner, delegate.publicKey, new BN(150));
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(200)
+    );
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      delegate,
+      new BN(100),
+      vaultAuthority
+    );
+
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        delegate,
+        new BN(100),
+        vaultAuthority
+      );
+      assert.fail("Withdrawing above the daily limit should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("DelegateLimitExceeded");
+    }
+
+    // the owner is not bound by the delegate's limit, but has to present the vault authority
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        new BN(100)
+      );
+      assert.fail("Withdrawing without the vault authority should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("MissingVaultAuthority");
+    }
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      new BN(100),
+      vaultAuthority
+    );
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    expect(vault.totalBalance.toNumber()).to.eql(0);
+  });
+
+  it("Large withdrawals need the co-signers' signatures", async () => {
+    await createVault(vaultOwner);
+    const coSigner = Keypair.generate();
+    const vaultAuthority = await createVaultAuthority(
+      vaultOwner,
+      [coSigner.publicKey],
+      2,
+      new BN(50)
+    );
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(200)
+    );
+
+    // withdrawals up to the threshold amount only need the owner
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      new BN(50),
+      vaultAuthority
+    );
+
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        new BN(150),
+        vaultAuthority
+      );
+      assert.fail("A large withdrawal without the co-signer should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("NotEnoughSigners");
+    }
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      new BN(150),
+      vaultAuthority,
+      [coSigner]
+    );
+
+    await closeVault(vaultOwner, vaultAuthority);
+    expect(await connection.getAccountInfo(vaultAuthority)).to.be.null;
+  });
+
+  it("Splitting a large withdrawal still needs the co-signers' signatures", async () => {
+    await createVault(vaultOwner);
+    const coSigner = Keypair.generate();
+    const vaultAuthority = await createVaultAuthority(
+      vaultOwner,
+      [coSigner.publicKey],
+      2,
+      new BN(100)
+    );
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(200)
+    );
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      new BN(60),
+      vaultAuthority
+    );
+
+    // the day's withdrawals would pass the threshold amount
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        new BN(60),
+        vaultAuthority
+      );
+      assert.fail("A split withdrawal without the co-signer should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("NotEnoughSigners");
+    }
+
+    await withdrawSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      vaultOwner,
+      new BN(140),
+      vaultAuthority,
+      [coSigner]
+    );
+
+    await closeVault(vaultOwner, vaultAuthority);
+  });
+
+  it("A non-admin cannot mint tokens", async () => {
+    try {
+      await program.methods
+        .mintTokensTo(new BN(1000))
+        .accounts({
+          mint: mint,
+          destinationVaultSplTokenAccount: testVaultSplTokenAccount.address,
+          adminVault: adminVaultPDA,
+          mintConfig: getMintConfigPDA(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          programInfo: programInfoPDA,
+          admin: testVaultOwner.publicKey,
+        })
+        .signers([testVaultOwner])
+        .rpc();
+      assert.fail("Minting by non-admin should have failed");
+    } catch (e: any) {
+      console.log(`Expected error due to non-admin mint: ${e}`);
+    }
+  });
+
+  it("Cannot mint more than the epoch's mint cap", async () => {
+    const mintConfig = await program.account.mintConfig.fetch(
+      getMintConfigPDA()
+    );
+    await setMintCap(mintConfig.mintedInEpoch.add(new BN(1000)));
+
+    try {
+      await program.methods
+        .mintTokensTo(new BN(1001))
+        .accounts({
+          mint: mint,
+          destinationVaultSplTokenAccount: testVaultSplTokenAccount.address,
+          adminVault: adminVaultPDA,
+          mintConfig: getMintConfigPDA(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          programInfo: programInfoPDA,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Minting above the cap should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("MintCapExceeded");
+    } finally {
+      await setMintCap(new BN("18446744073709551615")); // uncapped
+    }
+  });
+
+  it("Cannot distribute interest cause a month has not passed", async () => {
+    await createVault(vaultOwner);
+
+    const amountToDeposit = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amountToDeposit
+    );
+
+    // Fetch the initial balance of the vault
+    const initialBalance = await connection.getTokenAccountBalance(
+      vaultSplTokenAccount
+    );
+
+    try {
+      await distributeInterest(vaultPDA, vaultSplTokenAccount);
+
+      // Uncomment to test distribution
+      // NOTE: Do comment the time bound code inside instruction `distribute_interest.rs`
+      // const expectedNewBalance = initialBalance.value.uiAmount + initialBalance.value.uiAmount * 0.01;
+      //
+      // // Fetch the balance after distributing interest
+      // const updatedVaultBalance =
+      //     await connection.getTokenAccountBalance(vaultSplTokenAccount);
+      //
+      // // Assert that the balance has not changed
+      // expect(updatedVaultBalance.value.uiAmount)
+      //     .to.equal(expectedNewBalance, "Vault balance should have increased by 1%");
+
+      assert.fail(
+        "Interest distribution should have failed due to time constraint"
+      );
+    } catch (e) {
+      console.log(`Expected error due to time constraint: ${e}`);
+    }
+
+    const updatedBalance = await connection.getTokenAccountBalance(
+      vaultSplTokenAccount
+    );
+    expect(updatedBalance.value.uiAmount).to.eql(
+      initialBalance.value.uiAmount,
+      "Vault balance should not have changed due to time constraint"
+    );
+  });
+
+  it("Deposits into a token account other than the vault's are rejected", async () => {
+    await createVault(vaultOwner);
+
+    try {
+      await depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        testVaultSplTokenAccount.address,
+        testVaultOwner,
+        new BN(100)
+      );
+      assert.fail("Depositing into a foreign token account should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidVaultTokenAccount");
+    }
+  });
+
+  it("Withdrawals from a token account other than the vault's are rejected", async () => {
+    await createVault(vaultOwner);
+
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(100)
+    );
+
+    try {
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        adminSplTokenAccount.address,
+        testVaultOwner,
+        vaultOwner,
+        new BN(100)
+      );
+      assert.fail("Withdrawing from a foreign token account should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidVaultTokenAccount");
+    }
+  });
+
+  it("Interest cannot be paid into a token account other than the vault's", async () => {
+    await createVault(vaultOwner);
+
+    try {
+      await distributeInterest(vaultPDA, testVaultSplTokenAccount.address);
+      assert.fail("Paying into a foreign token account should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidVaultTokenAccount");
+    }
+  });
+
+  it("Batch distribution skips vaults that are not due", async () => {
+    await createVault(vaultOwner);
+
+    const amountToDeposit = new BN(100);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      amountToDeposit
+    );
+
+    const vaultBefore = await program.account.vault.fetch(vaultPDA);
+    await distributeInterestBatch([[vaultPDA, vaultSplTokenAccount]]);
+    const vaultAfter = await program.account.vault.fetch(vaultPDA);
+
+    expect(vaultAfter.lastInterestAt.toNumber()).to.eql(
+      vaultBefore.lastInterestAt.toNumber(),
+      "Vaults that are not due should not be paid"
+    );
+  });
+
+  it("Batch distribution rejects mismatched vault pairs", async () => {
+    await createVault(vaultOwner);
+
+    try {
+      await distributeInterestBatch([
+        [vaultPDA, testVaultSplTokenAccount.address],
+      ]);
+      assert.fail("A pair with a foreign token account should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidBatchAccounts");
+    }
+  });
+
+  it("Interest is still distributed after the admin role is handed over", async () => {
+    await createVault(vaultOwner);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(100)
+    );
+
+    const newAdmin = await generateFundedKeypair();
+    await handOverAdmin(admin, newAdmin);
+    try {
+      // the reserve vault still records the previous admin as its owner
+      const adminVault = await program.account.vault.fetch(adminVaultPDA);
+      expect(adminVault.owner).to.eql(admin.publicKey);
+
+      await distributeInterestBatch([[vaultPDA, vaultSplTokenAccount]]);
+    } finally {
+      await handOverAdmin(newAdmin, admin);
+    }
+  });
+
+  it("Deposits and withdrawals are tracked in the mint stats", async () => {
     await createVault(vaultOwner);
+    const mintStatsBefore = await program.account.mintStats.fetch(
+      getMintStatsPDA()
+    );
 
-    const amountToDeposit = new BN(100);
     await depositSPLTokens(
       testVaultSplTokenAccount.address,
-      vaultSplTokenAccount.address,
+      vaultSplTokenAccount,
       testVaultOwner,
-      amountToDeposit
+      new BN(100)
     );
 
     const vault = await program.account.vault.fetch(vaultPDA);
-    expect(vault.totalBalance.toNumber()).to.eql(
-      100,
-      "Vault's total balance should be 100 after deposit"
+    const mintStatsAfter = await program.account.mintStats.fetch(
+      getMintStatsPDA()
     );
+    expect(
+      mintStatsAfter.totalDeposits.sub(mintStatsBefore.totalDeposits).toNumber()
+    ).to.eql(100);
+    expect(
+      mintStatsAfter.totalShares.sub(mintStatsBefore.totalShares).toString()
+    ).to.eql(vault.shares.toString());
+  });
 
-    const vaultSplTokenAccountBalance = await connection.getTokenAccountBalance(
-      vaultSplTokenAccount.address
+  it("Starts an interest round that covers the owed interest", async () => {
+    await startInterestRound();
+
+    const mintStats = await program.account.mintStats.fetch(getMintStatsPDA());
+    const reserve = await getAccount(connection, adminSplTokenAccount.address);
+    expect(mintStats.roundStartedAt.toNumber()).to.be.greaterThan(0);
+    expect(mintStats.roundReserve.toString()).to.eql(reserve.amount.toString());
+    expect(mintStats.roundPayoutRatio.toString()).to.eql(
+      "1000000000",
+      "A funded reserve should pay the owed interest in full"
     );
-    console.log(
-      `Vault SPL token account balance after deposit: ${vaultSplTokenAccountBalance.value.uiAmount}`
+
+    try {
+      await startInterestRound();
+      assert.fail("Starting a second round in the same period should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InterestRoundInProgress");
+    }
+  });
+
+  it("Only the admin can start an interest round", async () => {
+    const notAdmin = await generateFundedKeypair();
+
+    try {
+      await startInterestRound(notAdmin);
+      assert.fail("Starting a round by non-admin should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("ConstraintHasOne");
+    }
+  });
+
+  it("Records the interest shortfall when the reserve cannot cover a round", async () => {
+    const { shortMint, deposit } = await setupShortReserve();
+
+    const mintStatsAtStart = await program.account.mintStats.fetch(
+      getMintStatsPDA(shortMint)
     );
-    expect(vaultSplTokenAccountBalance.value.uiAmount).to.eql(
-      100 / 10 ** 6,
-      "On-chain token account balance should match the deposited amount"
+    expect(mintStatsAtStart.roundReserve.toNumber()).to.eql(1);
+    expect(mintStatsAtStart.roundInterestOwed.toNumber()).to.be.greaterThan(1);
+    expect(mintStatsAtStart.roundPayoutRatio.toNumber()).to.be.lessThan(
+      1_000_000_000,
+      "A short reserve should pay out less than the owed interest"
+    );
+
+    // the next deposit pays the vault's interest at the round's ratio
+    const sharesBefore = (await program.account.vault.fetch(vaultPDA)).shares;
+    await deposit(new BN(1));
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    const mintStats = await program.account.mintStats.fetch(
+      getMintStatsPDA(shortMint)
+    );
+    expect(vault.interestShortfall.toNumber()).to.be.greaterThan(
+      0,
+      "The interest the reserve could not cover should be recorded on the vault"
     );
+    expect(mintStats.roundShortfall.toString()).to.eql(
+      vault.interestShortfall.toString()
+    );
+    expect(vault.shares.gte(sharesBefore)).to.be.true;
+    expect((await pendingInterest(shortMint)).gte(vault.interestShortfall)).to
+      .be.true;
   });
 
-  it("User can withdraw funds", async () => {
+  it("Keeps the round shortfall as a claim when the reserve is topped up", async () => {
+    const { shortMint, shortReserve, deposit } = await setupShortReserve();
+    await deposit(new BN(1));
+    const shortfallBefore = (await program.account.vault.fetch(vaultPDA))
+      .interestShortfall;
+
+    // the admin tops the reserve up while the round is still running
+    await mintTo(
+      connection,
+      admin,
+      shortMint,
+      shortReserve.address,
+      admin,
+      1_000_000_000_000
+    );
+    await deposit(new BN(1));
+
+    const vault = await program.account.vault.fetch(vaultPDA);
+    const mintStats = await program.account.mintStats.fetch(
+      getMintStatsPDA(shortMint)
+    );
+    expect(vault.interestShortfall.toNumber()).to.be.greaterThan(0);
+    expect(mintStats.roundShortfall.toString()).to.eql(
+      vault.interestShortfall.toString(),
+      "Paying the vault again should replace its shortfall rather than add to it"
+    );
+    expect(vault.interestShortfall.gte(shortfallBefore)).to.be.true;
+    expect((await pendingInterest(shortMint)).gte(vault.interestShortfall)).to
+      .be.true;
+  });
+
+  it("Cannot close a vault with interest pending", async () => {
+    const { shortMint, shortReserve } = await setupShortReserve();
+    const [shortAdminVault] = getAdminVaultPDA(shortMint);
+    const shortVaultSplTokenAccount = getAssociatedTokenAddressSync(
+      shortMint,
+      vaultPDA,
+      true
+    );
+    const userShortAccount = getAssociatedTokenAddressSync(
+      shortMint,
+      testVaultOwner.publicKey
+    );
+
+    const withdraw = (amount: BN) =>
+      program.methods
+        .withdrawSplTokens(amount)
+        .accounts({
+          vault: vaultPDA,
+          authority: vaultOwner.publicKey,
+          vaultAuthority: null,
+          payer: vaultOwner.publicKey,
+          userSplTokenAccount: userShortAccount,
+          vaultSplTokenAccount: shortVaultSplTokenAccount,
+          mint: shortMint,
+          mintConfig: getMintConfigPDA(shortMint),
+          mintStats: getMintStatsPDA(shortMint),
+          feeSplTokenAccount: getFeeTokenAccount(shortMint),
+          programInfo: programInfoPDA,
+          adminVault: shortAdminVault,
+          adminVaultSplTokenAccount: shortReserve.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([vaultOwner])
+        .rpc();
+
+    // withdrawing first pays what the single unit reserve holds, the rest of the interest
+    // stays owed
+    let { totalBalance } = await program.account.vault.fetch(vaultPDA);
+    while (totalBalance.gtn(0)) {
+      await withdraw(totalBalance);
+      ({ totalBalance } = await program.account.vault.fetch(vaultPDA));
+    }
+    expect((await pendingInterest(shortMint)).toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .closeVault()
+        .accounts({
+          vault: vaultPDA,
+          vaultAuthority: null,
+          vaultSplTokenAccount: shortVaultSplTokenAccount,
+          userSplTokenAccount: userShortAccount,
+          owner: vaultOwner.publicKey,
+          mint: shortMint,
+          mintConfig: getMintConfigPDA(shortMint),
+          mintStats: getMintStatsPDA(shortMint),
+          programInfo: programInfoPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([vaultOwner])
+        .rpc();
+      assert.fail("Closing a vault with interest pending should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InterestPending");
+    }
+  });
+
+  it("Deposits and withdrawals pay the protocol fee into the fee account", async () => {
     await createVault(vaultOwner);
+    const mintStatsBefore = await program.account.mintStats.fetch(
+      getMintStatsPDA()
+    );
 
-    const amount = new BN(100);
+    await setFeeParams(100, 200); // 1% on deposits, 2% on withdrawals
+    try {
+      await depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        new BN(10000)
+      );
 
-    await depositSPLTokens(
-      testVaultSplTokenAccount.address,
-      vaultSplTokenAccount.address,
-      testVaultOwner,
-      amount
+      let vault = await program.account.vault.fetch(vaultPDA);
+      expect(vault.totalBalance.toNumber()).to.eql(
+        9900,
+        "The vault should be credited the deposit net of the fee"
+      );
+      expect(vault.feesPaid.toNumber()).to.eql(100);
+
+      const userBalanceBefore = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+      await withdrawSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        vaultOwner,
+        new BN(5000)
+      );
+      const userBalanceAfter = await getAccount(
+        connection,
+        testVaultSplTokenAccount.address
+      );
+      expect(
+        Number(userBalanceAfter.amount - userBalanceBefore.amount)
+      ).to.eql(4900, "The user should receive the withdrawal net of the fee");
+
+      vault = await program.account.vault.fetch(vaultPDA);
+      expect(vault.totalBalance.toNumber()).to.eql(4900);
+      expect(vault.feesPaid.toNumber()).to.eql(200);
+    } finally {
+      await setFeeParams(0, 0);
+    }
+
+    const mintStatsAfter = await program.account.mintStats.fetch(
+      getMintStatsPDA()
     );
+    expect(
+      mintStatsAfter.uncollectedFees
+        .sub(mintStatsBefore.uncollectedFees)
+        .toNumber()
+    ).to.eql(200);
+  });
 
-    await withdrawSPLTokens(
-      testVaultSplTokenAccount.address,
-      vaultSplTokenAccount.address,
-      testVaultOwner,
-      vaultOwner,
-      amount
+  it("Admin collects the fees into the treasury", async () => {
+    const treasuryOwner = await generateFundedKeypair();
+    const treasury = await getOrCreateAssociatedTokenAccount(
+      connection,
+      treasuryOwner,
+      mint,
+      treasuryOwner.publicKey
+    );
+    const feeAccounts = {
+      programInfo: programInfoPDA,
+      admin: admin.publicKey,
+      mint: mint,
+      mintStats: getMintStatsPDA(),
+    };
+
+    await program.methods
+      .setTreasury()
+      .accounts({
+        ...feeAccounts,
+        treasurySplTokenAccount: treasury.address,
+      })
+      .signers([admin])
+      .rpc();
+
+    const { uncollectedFees } = await program.account.mintStats.fetch(
+      getMintStatsPDA()
     );
+    expect(uncollectedFees.toNumber()).to.be.greaterThan(0);
 
-    const vault = await program.account.vault.fetch(vaultPDA);
-    expect(vault.totalBalance.toNumber()).to.eql(
-      0,
-      "Vault's total balance should be 0 after withdrawal"
+    await program.methods
+      .collectFees()
+      .accounts({
+        ...feeAccounts,
+        feeSplTokenAccount: getFeeTokenAccount(),
+        treasurySplTokenAccount: treasury.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    const treasuryAfter = await getAccount(connection, treasury.address);
+    expect(treasuryAfter.amount.toString()).to.eql(uncollectedFees.toString());
+
+    const mintStats = await program.account.mintStats.fetch(getMintStatsPDA());
+    expect(mintStats.uncollectedFees.toNumber()).to.eql(0);
+    expect(mintStats.collectedFees.toString()).to.eql(
+      uncollectedFees.toString()
     );
   });
 
-  it("A user cannot withdraw someone else's funds", async () => {
+  it("Rejects fees above the maximum", async () => {
+    try {
+      await setFeeParams(1001, 0);
+      assert.fail("Setting a fee above 10% should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidFeeParams");
+    }
+  });
+
+  it("Deposits are held to the mint's deposit caps", async () => {
     await createVault(vaultOwner);
+    const { totalDeposits } = await program.account.mintStats.fetch(
+      getMintStatsPDA()
+    );
 
-    const amountToDeposit = new BN(100);
+    const deposit = async (amount: number) =>
+      depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        new BN(amount)
+      );
 
-    await depositSPLTokens(
-      testVaultSplTokenAccount.address,
-      vaultSplTokenAccount.address,
-      testVaultOwner,
-      amountToDeposit
+    await setDepositCaps(
+      new BN(1000),
+      totalDeposits.add(new BN(1500)),
+      new BN(100)
+    );
+    try {
+      await deposit(800);
+
+      const rejected: [number, string][] = [
+        [50, "DepositTooSmall"],
+        [300, "VaultBalanceCapExceeded"],
+      ];
+      for (const [amount, code] of rejected) {
+        try {
+          await deposit(amount);
+          assert.fail(`Depositing ${amount} should have failed`);
+        } catch (e: any) {
+          expect(e.error.errorCode.code).to.eql(code);
+        }
+      }
+
+      // a second vault has room of its own, but the mint does not
+      ({ vaultPDA, vaultOwner, vaultSplTokenAccount } = await setupVault());
+      await createVault(vaultOwner);
+      try {
+        await deposit(800);
+        assert.fail("Depositing above the mint's total cap should have failed");
+      } catch (e: any) {
+        expect(e.error.errorCode.code).to.eql("MintDepositCapExceeded");
+      }
+    } finally {
+      await setDepositCaps(U64_MAX, U64_MAX, new BN(0));
+    }
+  });
+
+  it("Registers the mint against its admin vault", async () => {
+    const mintConfig = await program.account.mintConfig.fetch(
+      getMintConfigPDA()
     );
+    expect(mintConfig.mint).to.eql(mint);
+    expect(mintConfig.reserveVault).to.eql(adminVaultPDA);
+    expect(mintConfig.decimals).to.eql(tokenDecimals);
+    expect(mintConfig.vaultsEnabled).to.be.true;
+    expect(mintConfig.depositsEnabled).to.be.true;
 
-    let vault = await program.account.vault.fetch(vaultPDA);
-    expect(vault.totalBalance.toNumber()).to.eql(100);
+    try {
+      await registerMint();
+      assert.fail("Registering a mint twice should have failed");
+    } catch (e: any) {
+      console.log(`Expected error due to re-registration: ${e}`);
+    }
+  });
+
+  it("Only the admin can create a mint's reserve vault", async () => {
+    const newMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      tokenDecimals
+    );
+    const reserveTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      newMint,
+      getAdminVaultPDA(newMint)[0],
+      true
+    );
+    const notAdmin = await generateFundedKeypair();
+
+    try {
+      await createAdminVault(newMint, reserveTokenAccount.address, notAdmin);
+      assert.fail("Creating a reserve vault by non-admin should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("ConstraintHasOne");
+    }
+  });
+
+  it("Vaults cannot be created for an unregistered mint", async () => {
+    const unregisteredMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      tokenDecimals
+    );
 
     try {
       await program.methods
-        .withdrawSplTokens(amountToDeposit)
+        .createVault(null)
         .accounts({
           vault: vaultPDA,
+          programInfo: programInfoPDA,
           owner: vaultOwner.publicKey,
-          payer: testVaultOwner.publicKey,
-          userSplTokenAccount: testVaultSplTokenAccount.address,
-          vaultSplTokenAccount: vaultSplTokenAccount.address,
+          mint: unregisteredMint,
+          mintConfig: getMintConfigPDA(unregisteredMint),
+          splTokenAccount: getAssociatedTokenAddressSync(
+            unregisteredMint,
+            vaultPDA,
+            true
+          ),
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([testVaultOwner])
+        .signers([vaultOwner])
         .rpc();
+      assert.fail("Creating a vault for an unregistered mint should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("AccountNotInitialized");
+    }
+  });
 
-      assert.fail("Withdrawal by non-owner should have failed");
+  it("Admin can disable new vaults and deposits for a mint", async () => {
+    await setMintEnabled(false, true);
+    try {
+      await createVault(vaultOwner);
+      assert.fail("Creating a vault for a disabled mint should have failed");
     } catch (e: any) {
-      console.log(`Expected error due to non-owner withdrawal: ${e}`);
+      expect(e.error.errorCode.code).to.eql("VaultsDisabled");
+    }
+
+    await setMintEnabled(true, false);
+    try {
+      await createVault(vaultOwner);
+      await depositSPLTokens(
+        testVaultSplTokenAccount.address,
+        vaultSplTokenAccount,
+        testVaultOwner,
+        new BN(100)
+      );
+      assert.fail("Depositing into a mint with deposits disabled should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("DepositsDisabled");
+    } finally {
+      await setMintEnabled(true, true);
     }
   });
 
-  it("Cannot distribute interest cause a month has not passed", async () => {
+  it("Owner can transfer funds to another user's vault", async () => {
     await createVault(vaultOwner);
-
-    const amountToDeposit = new BN(100);
     await depositSPLTokens(
       testVaultSplTokenAccount.address,
-      vaultSplTokenAccount.address,
+      vaultSplTokenAccount,
       testVaultOwner,
-      amountToDeposit
+      new BN(1000)
     );
-
-    // Fetch the initial balance of the vault
-    const initialBalance = await connection.getTokenAccountBalance(
-      vaultSplTokenAccount.address
+    const source = {
+      vault: vaultPDA,
+      owner: vaultOwner,
+      splTokenAccount: vaultSplTokenAccount,
+    };
+    const mintStatsBefore = await program.account.mintStats.fetch(
+      getMintStatsPDA()
     );
 
+    // the destination belongs to someone else
+    ({ vaultPDA, vaultOwner, vaultSplTokenAccount } = await setupVault());
+    await createVault(vaultOwner);
+
     try {
-      await distributeInterest(vaultPDA, vaultSplTokenAccount.address);
+      await transferBetweenVaults(source, vaultOwner, new BN(400));
+      assert.fail("Transferring out of someone else's vault should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("InvalidVaultOwner");
+    }
 
-      // Uncomment to test distribution
-      // NOTE: Do comment the time bound code inside instruction `distribute_interest.rs`
-      // const expectedNewBalance = initialBalance.value.uiAmount + initialBalance.value.uiAmount * 0.01;
-      //
-      // // Fetch the balance after distributing interest
-      // const updatedVaultBalance =
-      //     await connection.getTokenAccountBalance(vaultSplTokenAccount.address);
-      //
-      // // Assert that the balance has not changed
-      // expect(updatedVaultBalance.value.uiAmount)
-      //     .to.equal(expectedNewBalance, "Vault balance should have increased by 1%");
+    await transferBetweenVaults(source, source.owner, new BN(400));
 
-      assert.fail(
-        "Interest distribution should have failed due to time constraint"
-      );
-    } catch (e) {
-      console.log(`Expected error due to time constraint: ${e}`);
-    }
+    const sourceVault = await program.account.vault.fetch(source.vault);
+    const destinationVault = await program.account.vault.fetch(vaultPDA);
+    expect(sourceVault.totalBalance.toNumber()).to.eql(600);
+    expect(destinationVault.totalBalance.toNumber()).to.eql(400);
+    expect(destinationVault.shares.toNumber()).to.be.greaterThan(0);
 
-    const updatedBalance = await connection.getTokenAccountBalance(
-      vaultSplTokenAccount.address
+    const destinationTokenAccount = await getAccount(
+      connection,
+      vaultSplTokenAccount
     );
-    expect(updatedBalance.value.uiAmount).to.eql(
-      initialBalance.value.uiAmount,
-      "Vault balance should not have changed due to time constraint"
+    expect(Number(destinationTokenAccount.amount)).to.eql(400);
+
+    const mintStatsAfter = await program.account.mintStats.fetch(
+      getMintStatsPDA()
+    );
+    expect(mintStatsAfter.totalDeposits.toString()).to.eql(
+      mintStatsBefore.totalDeposits.toString(),
+      "Transfers between vaults should not change the mint's total deposits"
+    );
+  });
+
+  it("A vault cannot transfer to itself", async () => {
+    await createVault(vaultOwner);
+    await depositSPLTokens(
+      testVaultSplTokenAccount.address,
+      vaultSplTokenAccount,
+      testVaultOwner,
+      new BN(100)
     );
+
+    try {
+      await transferBetweenVaults(
+        {
+          vault: vaultPDA,
+          owner: vaultOwner,
+          splTokenAccount: vaultSplTokenAccount,
+        },
+        vaultOwner,
+        new BN(100)
+      );
+      assert.fail("Transferring to the same vault should have failed");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.eql("SameVault");
+    }
   });
 
   /** Helper functions */
@@ -241,11 +1921,11 @@ export default function suite() {
     const vaultPDA = findVaultPDA(programInfo.vaultsCount);
     const vaultOwner = await generateFundedKeypair();
 
-    const vaultSplTokenAccount = await getOrCreateAssociatedTokenAccount(
-      connection,
-      vaultOwner,
+    // created by `create_vault`, owned by the vault PDA
+    const vaultSplTokenAccount = getAssociatedTokenAddressSync(
       mint,
-      vaultOwner.publicKey
+      vaultPDA,
+      true
     );
 
     let adminSplTokenAccount: Account;
@@ -282,16 +1962,59 @@ export default function suite() {
     };
   }
 
-  function findVaultPDA(vaultNumber: number) {
+  function findVaultPDA(vaultNumber: number) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("vault"), toBytesInt32(vaultNumber)],
+      program.programId
+    )[0];
+  }
+
+  function findOwnerVaultPDA(
+    owner: PublicKey,
+    vaultMint: PublicKey,
+    nonce: number
+  ) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        encode("owner-vault"),
+        owner.toBuffer(),
+        vaultMint.toBuffer(),
+        toBytesInt32(nonce),
+      ],
+      program.programId
+    )[0];
+  }
+
+  function getMintStatsPDA(vaultMint: PublicKey = mint) {
     return anchor.web3.PublicKey.findProgramAddressSync(
-      [encode("vault"), toBytesInt32(vaultNumber)],
+      [encode("mint-stats"), vaultMint.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function getMintConfigPDA(vaultMint: PublicKey = mint) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("mint-config"), vaultMint.toBuffer()],
       program.programId
     )[0];
   }
 
-  function getAdminVaultPDA() {
+  // protocol fees are held in the mint stats' associated token account until collected
+  function getFeeTokenAccount(
+    vaultMint: PublicKey = mint,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    return getAssociatedTokenAddressSync(
+      vaultMint,
+      getMintStatsPDA(vaultMint),
+      true,
+      tokenProgram
+    );
+  }
+
+  function getAdminVaultPDA(vaultMint: PublicKey = mint) {
     return anchor.web3.PublicKey.findProgramAddressSync(
-      [encode("admin-vault")],
+      [encode("admin-vault"), vaultMint.toBuffer()],
       program.programId
     );
   }
@@ -299,45 +2022,13 @@ export default function suite() {
   async function preFundAdminVault() {
     const adminVaultFund = new BN(1000000 * 10 ** 6); // pre fund 1 million tokens
 
-    const tempAdminKey = await generateFundedKeypair();
-    const tempAdminSplTokenAccount = await getOrCreateAssociatedTokenAccount(
-      connection,
-      tempAdminKey,
-      mint,
-      tempAdminKey.publicKey
-    );
-    await mintTokens(adminVaultFund, tempAdminSplTokenAccount.address);
+    // the admin vault pays out interest from its token account, fund it directly
+    await mintTokens(adminVaultFund, adminSplTokenAccount.address);
 
-    let balance = await connection.getTokenAccountBalance(
+    const balance = await connection.getTokenAccountBalance(
       adminSplTokenAccount.address
     );
 
-    console.log(
-      "admin spl token account: ",
-      adminSplTokenAccount.address.toBase58(),
-      " balance: ",
-      balance.value.uiAmount
-    );
-
-    //  can have a separate rpc call to deposit tokens to admin vault
-    try {
-      await program.methods
-        .depositSplTokens(new BN(adminVaultFund))
-        .accounts({
-          userVault: adminVaultPDA,
-          userSplTokenAccount: tempAdminSplTokenAccount.address,
-          destinationVaultSplTokenAccount: adminSplTokenAccount.address,
-          feePayer: tempAdminKey.publicKey,
-          mint: mint,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([tempAdminKey])
-        .rpc();
-    } catch (e: any) {
-      console.log("Here is the error: ");
-      console.log(e);
-    }
-
     console.log(
       `Funded ${adminVaultFund}, balance ${
         balance.value.uiAmount
@@ -347,15 +2038,20 @@ export default function suite() {
 
   async function createVault(
     vaultOwner: Keypair,
-    splTokenAccount: Account = vaultSplTokenAccount
+    splTokenAccount: PublicKey = vaultSplTokenAccount,
+    lockDuration: BN | null = null
   ) {
     await program.methods
-      .createVault()
+      .createVault(lockDuration)
       .accounts({
         vault: vaultPDA,
         programInfo: programInfoPDA,
         owner: vaultOwner.publicKey,
-        splTokenAccount: splTokenAccount.address,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        splTokenAccount: splTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([vaultOwner])
@@ -364,20 +2060,178 @@ export default function suite() {
     return program.account.vault.fetch(vaultPDA);
   }
 
-  async function createAdminVault() {
+  async function closeVault(
+    owner: Keypair,
+    vaultAuthority: PublicKey | null = null
+  ) {
+    await program.methods
+      .closeVault()
+      .accounts({
+        vault: vaultPDA,
+        vaultAuthority: vaultAuthority,
+        vaultSplTokenAccount: vaultSplTokenAccount,
+        userSplTokenAccount: testVaultSplTokenAccount.address,
+        owner: owner.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
+        programInfo: programInfoPDA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  function findVaultAuthorityPDA(vault: PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [encode("vault-authority"), vault.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function createVaultAuthority(
+    owner: Keypair,
+    coSigners: PublicKey[],
+    threshold: number,
+    multisigThresholdAmount: BN
+  ) {
+    const vaultAuthority = findVaultAuthorityPDA(vaultPDA);
+
+    await program.methods
+      .createVaultAuthority(coSigners, threshold, multisigThresholdAmount)
+      .accounts({
+        vault: vaultPDA,
+        vaultAuthority: vaultAuthority,
+        owner: owner.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    return vaultAuthority;
+  }
+
+  async function setVaultDelegate(
+    owner: Keypair,
+    delegate: PublicKey,
+    dailyWithdrawalLimit: BN | null
+  ) {
+    await program.methods
+      .setVaultDelegate(delegate, dailyWithdrawalLimit)
+      .accounts({
+        vault: vaultPDA,
+        vaultAuthority: findVaultAuthorityPDA(vaultPDA),
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  async function createAdminVault(
+    vaultMint: PublicKey = mint,
+    splTokenAccount: PublicKey = adminSplTokenAccount.address,
+    signer: Keypair = admin
+  ) {
+    const [adminVault] = getAdminVaultPDA(vaultMint);
+
     await program.methods
       .createAdminVault()
       .accounts({
-        vault: adminVaultPDA,
+        vault: adminVault,
+        programInfo: programInfoPDA,
+        admin: signer.publicKey,
+        mint: vaultMint,
+        splTokenAccount: splTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+    return adminVault;
+  }
+
+  async function migrateVault(
+    vault: PublicKey,
+    vaultMint: PublicKey = mint,
+    signer: Keypair = admin
+  ) {
+    await program.methods
+      .migrateVault()
+      .accounts({
+        vault: vault,
+        programInfo: programInfoPDA,
+        admin: signer.publicKey,
+        mint: vaultMint,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function takeVaultCustody(
+    vault: PublicKey,
+    owner: Keypair,
+    legacySplTokenAccount: PublicKey,
+    splTokenAccount: PublicKey,
+    vaultMint: PublicKey
+  ) {
+    await program.methods
+      .takeVaultCustody()
+      .accounts({
+        vault: vault,
+        owner: owner.publicKey,
+        legacySplTokenAccount: legacySplTokenAccount,
+        splTokenAccount: splTokenAccount,
+        mint: vaultMint,
+        mintConfig: getMintConfigPDA(vaultMint),
+        mintStats: getMintStatsPDA(vaultMint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  // vaults can only be created for a mint once the admin has registered it
+  async function registerMint(
+    vaultMint: PublicKey = mint,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    interestRateBps = 100,
+    accrualPeriod = 30 * 24 * 60 * 60
+  ) {
+    await program.methods
+      .registerMint(interestRateBps, new BN(accrualPeriod))
+      .accounts({
         programInfo: programInfoPDA,
-        owner: admin.publicKey,
-        splTokenAccount: adminSplTokenAccount.address,
+        admin: admin.publicKey,
+        mint: vaultMint,
+        adminVault: getAdminVaultPDA(vaultMint)[0],
+        mintConfig: getMintConfigPDA(vaultMint),
+        mintStats: getMintStatsPDA(vaultMint),
+        feeSplTokenAccount: getFeeTokenAccount(vaultMint, tokenProgram),
+        tokenProgram: tokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
       .rpc();
+  }
 
-    return vaultPDA;
+  async function setMintEnabled(
+    vaultsEnabled: boolean,
+    depositsEnabled: boolean
+  ) {
+    await program.methods
+      .setMintEnabled(vaultsEnabled, depositsEnabled)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+      })
+      .signers([admin])
+      .rpc();
   }
 
   async function mintTokens(amount: BN, destinationSplTokenAddress: PublicKey) {
@@ -388,8 +2242,10 @@ export default function suite() {
           mint: mint,
           destinationVaultSplTokenAccount: destinationSplTokenAddress,
           adminVault: adminVaultPDA,
+          mintConfig: getMintConfigPDA(),
           tokenProgram: TOKEN_PROGRAM_ID,
-          feePayer: admin.publicKey,
+          programInfo: programInfoPDA,
+          admin: admin.publicKey,
         })
         .signers([admin])
         .rpc();
@@ -399,6 +2255,66 @@ export default function suite() {
     }
   }
 
+  async function handOverAdmin(currentAdmin: Keypair, newAdmin: Keypair) {
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: currentAdmin.publicKey,
+      })
+      .signers([currentAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({
+        programInfo: programInfoPDA,
+        newAdmin: newAdmin.publicKey,
+      })
+      .signers([newAdmin])
+      .rpc();
+  }
+
+  async function setPaused(paused: boolean, withdrawalsPaused: boolean) {
+    await program.methods
+      .setPaused(paused, withdrawalsPaused)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setLockParams(
+    earlyWithdrawalPenaltyBps: number,
+    lockedInterestBonusBps: number
+  ) {
+    await program.methods
+      .setLockParams(earlyWithdrawalPenaltyBps, lockedInterestBonusBps)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setMintCap(
+    mintCapPerEpoch: BN,
+    vaultMint: PublicKey = mint
+  ) {
+    await program.methods
+      .setMintCap(mintCapPerEpoch)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: vaultMint,
+        mintConfig: getMintConfigPDA(vaultMint),
+      })
+      .signers([admin])
+      .rpc();
+  }
+
   async function fundAccount(tokenAccountAddress: PublicKey, amount: number) {
     // Mint to the user's token account
     await mintTokens(new BN(amount), tokenAccountAddress);
@@ -415,46 +2331,283 @@ export default function suite() {
   async function depositSPLTokens(
     fromTokenAddress: PublicKey,
     vaultTokenAddress: PublicKey,
-    payer: Keypair,
-    amountToDeposit: BN
+    depositor: Keypair,
+    amountToDeposit: BN,
+    memo: string | null = null
   ) {
     await fundAccount(
       fromTokenAddress,
       1000 * 10 ** 6
     );
 
-    await program.methods
-      .depositSplTokens(amountToDeposit)
+    await depositInstruction(
+      fromTokenAddress,
+      vaultTokenAddress,
+      depositor,
+      amountToDeposit,
+      memo
+    )
+      .signers([depositor])
+      .rpc();
+  }
+
+  function depositInstruction(
+    fromTokenAddress: PublicKey,
+    vaultTokenAddress: PublicKey,
+    depositor: Keypair,
+    amountToDeposit: BN,
+    memo: string | null = null
+  ) {
+    return program.methods
+      .depositSplTokens(amountToDeposit, memo)
       .accounts({
         userVault: vaultPDA,
-        feePayer: payer.publicKey,
+        depositor: depositor.publicKey,
         userSplTokenAccount: fromTokenAddress,
         destinationVaultSplTokenAccount: vaultTokenAddress,
         mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
+        feeSplTokenAccount: getFeeTokenAccount(),
+        programInfo: programInfoPDA,
+        adminVault: adminVaultPDA,
+        adminVaultSplTokenAccount: adminSplTokenAccount.address,
         tokenProgram: TOKEN_PROGRAM_ID,
-      })
-      .signers([payer])
-      .rpc();
+      });
   }
 
   async function withdrawSPLTokens(
     toTokenAddress: PublicKey,
     vaultTokenAddress: PublicKey,
     feePayer: Keypair,
-    vaultOwner: Keypair,
-    amountToWithdraw: BN
+    authority: Keypair,
+    amountToWithdraw: BN,
+    vaultAuthority: PublicKey | null = null,
+    coSigners: Keypair[] = []
   ) {
     await program.methods
       .withdrawSplTokens(amountToWithdraw)
       .accounts({
         vault: vaultPDA,
-        owner: vaultOwner.publicKey,
+        authority: authority.publicKey,
+        vaultAuthority: vaultAuthority,
         payer: feePayer.publicKey,
         userSplTokenAccount: toTokenAddress,
         vaultSplTokenAccount: vaultTokenAddress,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
+        feeSplTokenAccount: getFeeTokenAccount(),
+        programInfo: programInfoPDA,
+        adminVault: adminVaultPDA,
+        adminVaultSplTokenAccount: adminSplTokenAccount.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        coSigners.map((coSigner) => ({
+          pubkey: coSigner.publicKey,
+          isSigner: true,
+          isWritable: false,
+        }))
+      )
+      .signers([feePayer, authority, ...coSigners])
+      .rpc();
+  }
+
+  // moves funds from `source` into the vault the helpers currently point at
+  async function transferBetweenVaults(
+    source: { vault: PublicKey; owner: Keypair; splTokenAccount: PublicKey },
+    owner: Keypair,
+    amount: BN
+  ) {
+    await program.methods
+      .transferBetweenVaults(amount)
+      .accounts({
+        sourceVault: source.vault,
+        sourceVaultSplTokenAccount: source.splTokenAccount,
+        destinationVault: vaultPDA,
+        destinationVaultSplTokenAccount: vaultSplTokenAccount,
+        owner: owner.publicKey,
+        vaultAuthority: null,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
+        programInfo: programInfoPDA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  async function setFeeParams(
+    depositFeeBps: number,
+    withdrawalFeeBps: number
+  ) {
+    await program.methods
+      .setFeeParams(depositFeeBps, withdrawalFeeBps)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setDepositCaps(
+    maxVaultBalance: BN,
+    maxTotalDeposits: BN,
+    minDeposit: BN
+  ) {
+    await program.methods
+      .setDepositCaps(maxVaultBalance, maxTotalDeposits, minDeposit)
+      .accounts({
+        programInfo: programInfoPDA,
+        admin: admin.publicKey,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function startInterestRound(
+    signer: Keypair = admin,
+    roundMint: PublicKey = mint,
+    reserve: PublicKey = adminSplTokenAccount.address
+  ) {
+    await program.methods
+      .startInterestRound()
+      .accounts({
+        adminVault: getAdminVaultPDA(roundMint)[0],
+        adminVaultSplTokenAccount: reserve,
+        mint: roundMint,
+        mintConfig: getMintConfigPDA(roundMint),
+        mintStats: getMintStatsPDA(roundMint),
+        programInfo: programInfoPDA,
+        admin: signer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  // interest owed to the vault at vaultPDA, the value of its shares above its balance
+  async function pendingInterest(vaultMint: PublicKey = mint) {
+    const vault = await program.account.vault.fetch(vaultPDA);
+    const mintConfig = await program.account.mintConfig.fetch(
+      getMintConfigPDA(vaultMint)
+    );
+    return vault.shares
+      .mul(mintConfig.interestIndex)
+      .div(new BN(10).pow(new BN(12)))
+      .sub(vault.totalBalance);
+  }
+
+  // a mint of its own earning 100% a day, so interest is owed within seconds, with a vault at
+  // vaultPDA holding a large deposit and a round started against a reserve of a single unit
+  async function setupShortReserve() {
+    const shortMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      tokenDecimals
+    );
+    const [shortAdminVault] = getAdminVaultPDA(shortMint);
+    const shortReserve = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      shortMint,
+      shortAdminVault,
+      true
+    );
+    await createAdminVault(shortMint, shortReserve.address);
+    await registerMint(shortMint, TOKEN_PROGRAM_ID, 10_000, 24 * 60 * 60);
+
+    const shortVaultSplTokenAccount = getAssociatedTokenAddressSync(
+      shortMint,
+      vaultPDA,
+      true
+    );
+    await program.methods
+      .createVault(null)
+      .accounts({
+        vault: vaultPDA,
+        programInfo: programInfoPDA,
+        owner: vaultOwner.publicKey,
+        mint: shortMint,
+        mintConfig: getMintConfigPDA(shortMint),
+        splTokenAccount: shortVaultSplTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([vaultOwner])
+      .rpc();
+
+    const userShortAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      testVaultOwner,
+      shortMint,
+      testVaultOwner.publicKey
+    );
+    await mintTo(
+      connection,
+      admin,
+      shortMint,
+      userShortAccount.address,
+      admin,
+      2_000_000_000_000
+    );
+    const deposit = (amount: BN) =>
+      program.methods
+        .depositSplTokens(amount, null)
+        .accounts({
+          userVault: vaultPDA,
+          depositor: testVaultOwner.publicKey,
+          userSplTokenAccount: userShortAccount.address,
+          destinationVaultSplTokenAccount: shortVaultSplTokenAccount,
+          mint: shortMint,
+          mintConfig: getMintConfigPDA(shortMint),
+          mintStats: getMintStatsPDA(shortMint),
+          feeSplTokenAccount: getFeeTokenAccount(shortMint),
+          programInfo: programInfoPDA,
+          adminVault: shortAdminVault,
+          adminVaultSplTokenAccount: shortReserve.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([testVaultOwner])
+        .rpc();
+
+    await deposit(new BN(1_000_000_000_000));
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    await mintTo(connection, admin, shortMint, shortReserve.address, admin, 1);
+    await startInterestRound(admin, shortMint, shortReserve.address);
+
+    return { shortMint, shortReserve, deposit };
+  }
+
+  async function distributeInterestBatch(vaults: [PublicKey, PublicKey][]) {
+    await program.methods
+      .distributeInterestBatch()
+      .accounts({
+        adminVault: adminVaultPDA,
+        adminVaultSplTokenAccount: adminSplTokenAccount.address,
+        mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
+        programInfo: programInfoPDA,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([feePayer, vaultOwner])
+      .remainingAccounts(
+        vaults.flatMap(([vault, vaultSplTokenAccount]) => [
+          { pubkey: vault, isSigner: false, isWritable: true },
+          { pubkey: vaultSplTokenAccount, isSigner: false, isWritable: true },
+        ])
+      )
       .rpc();
   }
 
@@ -479,6 +2632,8 @@ export default function suite() {
         adminVaultSplTokenAccount: adminSplTokenAccount.address,
         destinationVaultSplTokenAccount: destinationVaultSplTokenAccount,
         mint: mint,
+        mintConfig: getMintConfigPDA(),
+        mintStats: getMintStatsPDA(),
         programInfo: programInfoPDA,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -492,7 +2647,11 @@ export default function suite() {
     );
   }
 
-  async function mintToken(admin: Keypair, decimals = tokenDecimals) {
+  async function mintToken(
+    admin: Keypair,
+    decimals = tokenDecimals,
+    mintKeypair?: Keypair
+  ) {
     console.log("minting token");
 
     let mindAddress = await createMint(
@@ -500,7 +2659,8 @@ export default function suite() {
       admin,
       adminVaultPDA,
       null,
-      decimals
+      decimals,
+      mintKeypair
     );
 
     const mint = await getMint(connection, mindAddress);